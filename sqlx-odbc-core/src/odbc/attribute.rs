@@ -0,0 +1,154 @@
+//! Generic ODBC connection attributes.
+
+/// How the value of a connection attribute is passed to and returned by the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum AttributeKind {
+    Integer,
+    String,
+    Binary,
+    Pointer,
+}
+
+/// An ODBC connection attribute, as set with `SQLSetConnectAttr`.
+///
+/// Standard attributes are available as constants. Driver-specific attributes (e.g.
+/// `SQL_COPT_SS_*` for SQL Server) can be created from their numeric code with
+/// [`integer`](Self::integer), [`string`](Self::string), [`binary`](Self::binary) or
+/// [`pointer`](Self::pointer), depending on the type of their value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OdbcAttribute {
+    pub(crate) code: i32,
+    pub(crate) kind: AttributeKind,
+}
+
+impl OdbcAttribute {
+    /// `SQL_ATTR_ACCESS_MODE`
+    pub const ACCESS_MODE: Self = Self::integer(101);
+    /// `SQL_ATTR_AUTOCOMMIT`
+    ///
+    /// Transactions started through SQLx manage autocommit themselves; changing it while
+    /// one is open confuses the transaction manager.
+    pub const AUTOCOMMIT: Self = Self::integer(102);
+    /// `SQL_ATTR_LOGIN_TIMEOUT` (pre-connect only)
+    pub const LOGIN_TIMEOUT: Self = Self::integer(103);
+    /// `SQL_ATTR_TRACE`
+    pub const TRACE: Self = Self::integer(104);
+    /// `SQL_ATTR_TRACEFILE`
+    pub const TRACEFILE: Self = Self::string(105);
+    /// `SQL_ATTR_TXN_ISOLATION`
+    pub const TXN_ISOLATION: Self = Self::integer(108);
+    /// `SQL_ATTR_CURRENT_CATALOG`
+    pub const CURRENT_CATALOG: Self = Self::string(109);
+    /// `SQL_ATTR_PACKET_SIZE` (pre-connect only)
+    pub const PACKET_SIZE: Self = Self::integer(112);
+    /// `SQL_ATTR_CONNECTION_TIMEOUT`
+    pub const CONNECTION_TIMEOUT: Self = Self::integer(113);
+    /// `SQL_ATTR_CONNECTION_DEAD` (read-only)
+    pub const CONNECTION_DEAD: Self = Self::integer(1209);
+    /// `SQL_COPT_SS_ACCESS_TOKEN` (SQL Server, pre-connect only), see
+    /// [`OdbcConnectOptions::mssql_access_token`](crate::odbc::OdbcConnectOptions::mssql_access_token)
+    pub const MSSQL_ACCESS_TOKEN: Self = Self::pointer(1256);
+    /// `SQL_COPT_SS_RESET_CONNECTION` (SQL Server), makes the driver run
+    /// `sp_reset_connection` before the next statement, see
+    /// [`OdbcConnection::reset`](crate::odbc::OdbcConnection::reset)
+    pub const MSSQL_RESET_CONNECTION: Self = Self::integer(1246);
+
+    /// An attribute with an integer value
+    pub const fn integer(code: i32) -> Self {
+        Self { code, kind: AttributeKind::Integer }
+    }
+
+    /// An attribute with a string value
+    pub const fn string(code: i32) -> Self {
+        Self { code, kind: AttributeKind::String }
+    }
+
+    /// An attribute with a binary value
+    pub const fn binary(code: i32) -> Self {
+        Self { code, kind: AttributeKind::Binary }
+    }
+
+    /// An attribute with a binary value the driver reads through a plain pointer
+    /// (`SQL_IS_POINTER`), e.g. a length-prefixed struct
+    pub const fn pointer(code: i32) -> Self {
+        Self { code, kind: AttributeKind::Pointer }
+    }
+
+    /// The numeric code of the attribute
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+}
+
+/// The value of a connection attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OdbcAttributeValue {
+    /// An integer value (`SQLUINTEGER` or `SQLULEN`)
+    Integer(u64),
+    /// A string value
+    String(String),
+    /// A binary value
+    Binary(Vec<u8>),
+}
+
+impl OdbcAttributeValue {
+    /// Get the value as an integer, if it is one
+    pub fn as_integer(&self) -> Option<u64> {
+        match self {
+            Self::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a string, if it is one
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as bytes, if it is binary
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Binary(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<u32> for OdbcAttributeValue {
+    fn from(value: u32) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<u64> for OdbcAttributeValue {
+    fn from(value: u64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<bool> for OdbcAttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<&str> for OdbcAttributeValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for OdbcAttributeValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<Vec<u8>> for OdbcAttributeValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Binary(value)
+    }
+}
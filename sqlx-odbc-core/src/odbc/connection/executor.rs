@@ -1,12 +1,15 @@
 //! Executor implementation for ODBC connections.
 
+use super::ConnectionHandle;
+use crate::odbc::error::convert;
 use crate::odbc::{
-    Odbc, OdbcColumn, OdbcConnection, OdbcQueryResult, OdbcRow, OdbcStatement,
-    OdbcTypeInfo, OdbcValue, OdbcValueData,
+    Odbc, OdbcColumn, OdbcConnection, OdbcEncoding, OdbcQueryResult, OdbcRow, OdbcStatement,
+    OdbcTimestampOffset, OdbcTypeInfo, OdbcValue, OdbcValueData,
 };
 use futures_core::stream::BoxStream;
 use futures_util::StreamExt;
-use odbc_api::{Cursor, ResultSetMetadata, SharedConnection};
+use odbc_api::handles::Statement;
+use odbc_api::{Cursor, ResultSetMetadata};
 use sqlx_core::describe::Describe;
 use sqlx_core::error::Error;
 use sqlx_core::executor::{Execute, Executor};
@@ -29,10 +32,12 @@ impl<'c> Executor<'c> for &'c mut OdbcConnection {
     {
         let sql = query.sql().to_string();
         let _arguments = query.take_arguments();
-        let conn = self.conn.clone();
+        let encoding = self.options.encoding;
 
         Box::pin(async_stream::try_stream! {
-            let rows = execute_query(conn, sql).await?;
+            let rows = self
+                .with_conn("fetch_many", move |conn| execute_query(conn, &sql, encoding))
+                .await?;
             for row in rows {
                 yield Either::Right(row);
             }
@@ -82,104 +87,114 @@ impl<'c> Executor<'c> for &'c mut OdbcConnection {
         'c: 'e,
     {
         let sql = sql.to_string();
-        let conn = self.conn.clone();
+        let encoding = self.options.encoding;
 
         Box::pin(async move {
-            let result = tokio::task::spawn_blocking(move || {
-                describe_query(conn, sql)
-            })
-            .await
-            .map_err(|_| Error::WorkerCrashed)??;
-
-            Ok(result)
+            self.with_conn("describe", move |conn| describe_query(conn, &sql, encoding))
+                .await
         })
     }
 }
 
 /// Execute a query and return rows (using persistent connection)
-async fn execute_query(
-    conn: SharedConnection<'static>,
-    sql: String,
+fn execute_query(
+    conn: &mut ConnectionHandle,
+    sql: &str,
+    encoding: OdbcEncoding,
 ) -> Result<Vec<OdbcRow>, Error> {
-    tokio::task::spawn_blocking(move || {
-        let conn_guard = conn.lock().map_err(|_| {
-            Error::Protocol("Failed to lock ODBC connection".into())
-        })?;
-
-        // Execute the query using the persistent connection
-        match conn_guard.execute(&sql, (), None) {
-            Ok(Some(mut cursor)) => {
-                let mut rows = Vec::new();
-                
-                // Get column info
-                let num_cols = cursor.num_result_cols()
-                    .map_err(|e| Error::Protocol(e.to_string()))? as usize;
+    // Execute the query using the persistent connection
+    match conn.execute(sql) {
+        Ok(Some(mut cursor)) => {
+            let mut rows = Vec::new();
+            
+            // Get column info
+            let num_cols = cursor.num_result_cols()
+                .map_err(convert)? as usize;
+            
+            let mut columns = Vec::with_capacity(num_cols);
+            for i in 1..=num_cols {
+                let mut desc = odbc_api::ColumnDescription::default();
+                cursor.describe_col(i as u16, &mut desc)
+                    .map_err(convert)?;
                 
-                let mut columns = Vec::with_capacity(num_cols);
+                let name = encoding
+                    .decode_name(&desc.name)
+                    .map_err(|e| Error::Decode(format!("name of column {i}: {e}").into()))?;
+                columns.push(OdbcColumn::new(
+                    i - 1,
+                    name,
+                    OdbcTypeInfo::new(desc.data_type),
+                ));
+            }
+
+            let mut narrow = Vec::new();
+            let mut wide = Vec::new();
+
+            // Fetch all rows
+            while let Some(mut row) = cursor.next_row()
+                .map_err(convert)? 
+            {
+                let mut values = Vec::with_capacity(num_cols);
                 for i in 1..=num_cols {
-                    let mut desc = odbc_api::ColumnDescription::default();
-                    cursor.describe_col(i as u16, &mut desc)
-                        .map_err(|e| Error::Protocol(e.to_string()))?;
+                    let column = &columns[i - 1];
+
+                    // Read as string for simplicity (should use proper type handling)
+                    let text = if encoding.reads_wide(column.type_info.data_type()) {
+                        row.get_wide_text(i as u16, &mut wide)
+                            .map_err(convert)?
+                            .then(|| OdbcEncoding::decode_wide(&wide))
+                    } else {
+                        row.get_text(i as u16, &mut narrow)
+                            .map_err(convert)?
+                            .then(|| encoding.decode_narrow(&narrow))
+                    };
+
+                    let data = match text.transpose() {
+                        // Keep the offset of DATETIMEOFFSET values as a dedicated variant
+                        Ok(Some(text)) if column.type_info.is_timestamp_offset() => {
+                            match text.parse::<OdbcTimestampOffset>() {
+                                Ok(ts) => OdbcValueData::TimestampOffset(ts),
+                                Err(_) => OdbcValueData::Text(text),
+                            }
+                        }
+                        Ok(Some(text)) => OdbcValueData::Text(text),
+                        Ok(None) => OdbcValueData::Null,
+                        Err(e) => {
+                            return Err(Error::Decode(
+                                format!("column `{}`: {e}", column.name).into(),
+                            ));
+                        }
+                    };
                     
-                    let name = String::from_utf8_lossy(&desc.name).to_string();
-                    columns.push(OdbcColumn::new(
-                        i - 1,
-                        name,
-                        OdbcTypeInfo::new(desc.data_type),
+                    values.push(OdbcValue::new(
+                        data,
+                        column.type_info.clone(),
                     ));
                 }
-
-                // Fetch all rows
-                while let Some(mut row) = cursor.next_row()
-                    .map_err(|e| Error::Protocol(e.to_string()))? 
-                {
-                    let mut values = Vec::with_capacity(num_cols);
-                    for i in 1..=num_cols {
-                        // Read as string for simplicity (should use proper type handling)
-                        let mut buf = Vec::new();
-                        let has_value = row.get_text(i as u16, &mut buf)
-                            .map_err(|e| Error::Protocol(e.to_string()))?;
-                        
-                        let data = if has_value {
-                            OdbcValueData::Text(String::from_utf8_lossy(&buf).to_string())
-                        } else {
-                            OdbcValueData::Null
-                        };
-                        
-                        values.push(OdbcValue::new(
-                            data,
-                            columns[i - 1].type_info.clone(),
-                        ));
-                    }
-                    rows.push(OdbcRow::new(columns.clone(), values));
-                }
-
-                Ok(rows)
+                rows.push(OdbcRow::new(columns.clone(), values));
             }
-            Ok(None) => Ok(Vec::new()),
-            Err(e) => Err(Error::Protocol(e.to_string())),
+
+            Ok(rows)
         }
-    })
-    .await
-    .map_err(|_| Error::WorkerCrashed)?
+        Ok(None) => Ok(Vec::new()),
+        Err(e) => Err(convert(e)),
+    }
 }
 
 /// Describe a query to get column and parameter info (using persistent connection)
 fn describe_query(
-    conn: SharedConnection<'static>,
-    sql: String,
+    conn: &mut ConnectionHandle,
+    sql: &str,
+    encoding: OdbcEncoding,
 ) -> Result<Describe<Odbc>, Error> {
-    let conn_guard = conn.lock().map_err(|_| {
-        Error::Protocol("Failed to lock ODBC connection".into())
-    })?;
-
     // Prepare the statement to get metadata using persistent connection
-    let mut prepared = conn_guard.prepare(&sql)
-        .map_err(|e| Error::Protocol(e.to_string()))?;
+    let mut prepared = conn.prepare(sql)
+        .map_err(convert)?;
 
     // Get column information
     let num_cols = prepared.num_result_cols()
-        .map_err(|e| Error::Protocol(e.to_string()))? as usize;
+        .into_result(&prepared)
+        .map_err(convert)? as usize;
 
     let mut columns = Vec::with_capacity(num_cols);
     let mut nullable = Vec::with_capacity(num_cols);
@@ -187,9 +202,12 @@ fn describe_query(
     for i in 1..=num_cols {
         let mut desc = odbc_api::ColumnDescription::default();
         prepared.describe_col(i as u16, &mut desc)
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+            .into_result(&prepared)
+            .map_err(convert)?;
 
-        let name = String::from_utf8_lossy(&desc.name).to_string();
+        let name = encoding
+            .decode_name(&desc.name)
+            .map_err(|e| Error::Decode(format!("name of column {i}: {e}").into()))?;
         columns.push(OdbcColumn::new(
             i - 1,
             name,
@@ -200,12 +218,14 @@ fn describe_query(
 
     // Get parameter information
     let num_params = prepared.num_params()
-        .map_err(|e| Error::Protocol(e.to_string()))? as usize;
+        .into_result(&prepared)
+        .map_err(convert)? as usize;
 
     let mut parameters = Vec::with_capacity(num_params);
     for i in 1..=num_params {
         let param_desc = prepared.describe_param(i as u16)
-            .map_err(|e| Error::Protocol(e.to_string()))?;
+            .into_result(&prepared)
+            .map_err(convert)?;
         parameters.push(OdbcTypeInfo::new(param_desc.data_type));
     }
 
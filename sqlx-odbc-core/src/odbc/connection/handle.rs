@@ -0,0 +1,122 @@
+//! The connection handle owned by an `OdbcConnection`.
+
+use odbc_api::handles::{self, slice_to_cow_utf8, SqlText, State, Statement, StatementImpl};
+use odbc_api::sys::HDbc;
+use odbc_api::{CursorImpl, Error, Preallocated};
+use std::mem::ManuallyDrop;
+
+/// A connected ODBC connection handle.
+///
+/// We allocate and connect the handle ourselves so attributes can be set before connecting,
+/// and `odbc_api::Connection` cannot be built from such a handle. This covers the part of its
+/// API the crate needs.
+pub(crate) struct ConnectionHandle {
+    handle: ManuallyDrop<handles::Connection<'static>>,
+    /// Whether to disconnect without rolling back an open transaction first
+    abandoned: bool,
+}
+
+impl ConnectionHandle {
+    /// Take ownership of a connected handle, which is disconnected when dropped.
+    pub(crate) fn new(handle: handles::Connection<'static>) -> Self {
+        Self {
+            handle: ManuallyDrop::new(handle),
+            abandoned: false,
+        }
+    }
+
+    /// The raw handle.
+    pub(crate) fn as_sys(&self) -> HDbc {
+        self.handle.as_sys()
+    }
+
+    /// Execute a statement without parameters, returning a cursor if it produced a result set.
+    pub(crate) fn execute(
+        &self,
+        sql: &str,
+    ) -> Result<Option<CursorImpl<StatementImpl<'_>>>, Error> {
+        let statement = self.handle.allocate_statement().into_result(&*self.handle)?;
+        // SAFETY: the statement was just allocated, so nothing is bound to it.
+        unsafe { Preallocated::new(statement) }.into_cursor(sql, ())
+    }
+
+    /// Prepare a statement, e.g. to describe its columns and parameters.
+    pub(crate) fn prepare(&self, sql: &str) -> Result<StatementImpl<'_>, Error> {
+        let mut statement = self.handle.allocate_statement().into_result(&*self.handle)?;
+        statement
+            .prepare(&SqlText::new(sql))
+            .into_result(&statement)?;
+        Ok(statement)
+    }
+
+    /// Enable or disable autocommit mode.
+    pub(crate) fn set_autocommit(&self, enabled: bool) -> Result<(), Error> {
+        self.handle.set_autocommit(enabled).into_result(&*self.handle)
+    }
+
+    /// Commit the transaction opened in manual-commit mode.
+    pub(crate) fn commit(&self) -> Result<(), Error> {
+        self.handle.commit().into_result(&*self.handle)
+    }
+
+    /// Roll back the transaction opened in manual-commit mode.
+    pub(crate) fn rollback(&self) -> Result<(), Error> {
+        self.handle.rollback().into_result(&*self.handle)
+    }
+
+    /// Whether the driver noticed that the link is lost (`SQL_ATTR_CONNECTION_DEAD`).
+    pub(crate) fn is_dead(&self) -> Result<bool, Error> {
+        self.handle.is_dead().into_result(&*self.handle)
+    }
+
+    /// The name of the DBMS, as reported by the driver.
+    pub(crate) fn database_management_system_name(&self) -> Result<String, Error> {
+        let mut buf = Vec::new();
+        self.handle
+            .fetch_database_management_system_name(&mut buf)
+            .into_result(&*self.handle)?;
+        Ok(slice_to_cow_utf8(&buf).into_owned())
+    }
+
+    /// The catalog the connection currently uses.
+    pub(crate) fn current_catalog(&self) -> Result<String, Error> {
+        let mut buf = Vec::new();
+        self.handle
+            .fetch_current_catalog(&mut buf)
+            .into_result(&*self.handle)?;
+        Ok(slice_to_cow_utf8(&buf).into_owned())
+    }
+
+    /// Disconnect without rolling back an open transaction first once the handle is dropped.
+    ///
+    /// Used for connections that are known to be dead, where a rollback is a pointless round
+    /// trip.
+    pub(crate) fn abandon(&mut self) {
+        self.abandoned = true;
+    }
+}
+
+impl Drop for ConnectionHandle {
+    /// Disconnect, then free the handle.
+    ///
+    /// If disconnecting fails, the handle is leaked rather than freed: freeing a connected
+    /// handle is an error, which `handles::Connection` reports with a panic.
+    fn drop(&mut self) {
+        let mut result = self.handle.disconnect().into_result(&*self.handle);
+
+        if !self.abandoned
+            && let Err(Error::Diagnostics { record, .. }) = &result
+            && record.state == State::INVALID_STATE_TRANSACTION
+        {
+            // A transaction is still open; roll it back and try again
+            let _ = self.rollback();
+            result = self.handle.disconnect().into_result(&*self.handle);
+        }
+
+        match result {
+            // SAFETY: the handle is not used after this.
+            Ok(()) => unsafe { ManuallyDrop::drop(&mut self.handle) },
+            Err(e) => log::warn!("failed to disconnect ODBC connection, leaking its handle: {e}"),
+        }
+    }
+}
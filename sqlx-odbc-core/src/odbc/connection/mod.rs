@@ -1,14 +1,27 @@
 //! ODBC connection implementation.
 
-use crate::odbc::{Odbc, OdbcConnectOptions, OdbcQueryResult};
-use odbc_api::SharedConnection;
+use crate::odbc::dbms::OpenTransactionCheck;
+use crate::odbc::environment;
+use crate::odbc::error::{convert, is_fatal, with_dialect};
+use crate::odbc::ffi::{attr, RawConnection};
+use crate::odbc::options::connection_string_attributes;
+use crate::odbc::transaction::{PendingRollback, SavedTransactionSettings};
+use crate::odbc::{
+    encode_mssql_access_token, Odbc, OdbcAttribute, OdbcAttributeValue, OdbcConnectOptions, OdbcDbmsInfo, OdbcDialect,
+    OdbcQueryResult, OdbcTransactionOptions, OdbcTransactionState, OdbcTransactionStatus,
+};
+use odbc_api::Cursor;
 use sqlx_core::connection::Connection;
 use sqlx_core::error::Error;
 use sqlx_core::transaction::Transaction;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
 mod executor;
+mod handle;
+
+pub(crate) use handle::ConnectionHandle;
 
 type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -17,23 +30,38 @@ type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 /// ODBC uses a blocking C API, so we offload blocking calls to the runtime's blocking
 /// thread-pool via `spawn_blocking` and synchronize access with a mutex.
 pub struct OdbcConnection {
-    /// The underlying ODBC connection (wrapped for thread safety via SharedConnection)
-    pub(crate) conn: SharedConnection<'static>,
+    /// The underlying ODBC connection, shared with the blocking tasks that use it
+    pub(crate) conn: Arc<Mutex<ConnectionHandle>>,
     /// Connection options
     pub(crate) options: OdbcConnectOptions,
+    /// DBMS details queried at connect time
+    pub(crate) dbms_info: Arc<OdbcDbmsInfo>,
     /// Current transaction depth
     pub(crate) transaction_depth: usize,
-    /// Whether a rollback is needed
+    /// Whether a dropped transaction still has to be rolled back
     pub(crate) needs_rollback: bool,
+    /// Options for the next transaction started by `begin_with_options`
+    pub(crate) pending_transaction_options: Option<OdbcTransactionOptions>,
+    /// Whether the open transaction was started by a custom `BEGIN` statement and is
+    /// ended with SQL instead of `SQLEndTran`
+    pub(crate) explicit_transaction: bool,
+    /// Settings to restore when the current transaction ends
+    pub(crate) saved_transaction_settings: Option<SavedTransactionSettings>,
+    /// Whether a fatal error left the connection unusable
+    pub(crate) broken: bool,
+    /// Catalog selected when the connection was established, restored by `reset`
+    pub(crate) initial_catalog: Option<String>,
 }
 
-// SAFETY: OdbcConnection uses SharedConnection which wraps the connection in Arc<Mutex<>>
+// SAFETY: the connection handle is only used behind its mutex
 unsafe impl Send for OdbcConnection {}
 unsafe impl Sync for OdbcConnection {}
 
 impl std::fmt::Debug for OdbcConnection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OdbcConnection")
+            .field("dialect", &self.dbms_info.dialect)
+            .field("broken", &self.broken)
             .field("transaction_depth", &self.transaction_depth)
             .field("needs_rollback", &self.needs_rollback)
             .finish()
@@ -42,51 +70,189 @@ impl std::fmt::Debug for OdbcConnection {
 
 impl OdbcConnection {
     /// Execute a blocking operation on the connection
+    ///
+    /// Runs any rollback queued by a dropped transaction first. Fails immediately if the
+    /// connection is broken, and marks it broken if the operation fails with a fatal error.
     pub(crate) async fn with_conn<R, F, S>(&mut self, operation: S, f: F) -> Result<R, Error>
     where
         R: Send + 'static,
-        F: FnOnce(&mut odbc_api::Connection<'static>) -> Result<R, Error> + Send + 'static,
+        F: FnOnce(&mut ConnectionHandle) -> Result<R, Error> + Send + 'static,
         S: std::fmt::Display + Send + 'static,
     {
+        if self.broken {
+            return Err(Error::Protocol(format!(
+                "ODBC {}: connection is broken after a fatal error",
+                operation
+            )));
+        }
+
         let conn = self.conn.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut conn_guard = conn.lock().map_err(|_| {
-                Error::Protocol(format!("ODBC {}: failed to lock connection", operation))
-            })?;
-            f(&mut conn_guard)
+        let dialect = self.dialect();
+        let pending = PendingRollback::of(self);
+        let (rolled_back, result) = tokio::task::spawn_blocking(move || {
+            let mut conn_guard = match conn.lock() {
+                Ok(guard) => guard,
+                Err(_) => {
+                    let e = Error::Protocol(format!("ODBC {}: failed to lock connection", operation));
+                    return (false, Err(e));
+                }
+            };
+
+            let rolled_back = pending.is_some();
+            if let Some(pending) = pending
+                && let Err(e) = pending.execute(&conn_guard, dialect)
+            {
+                return (false, Err(convert(e)));
+            }
+
+            (rolled_back, f(&mut conn_guard))
         })
         .await
-        .map_err(|_| Error::WorkerCrashed)?
+        .unwrap_or_else(|_| (false, Err(Error::WorkerCrashed)));
+        let result = result.map_err(|e| with_dialect(e, dialect));
+
+        if rolled_back {
+            self.needs_rollback = false;
+            if self.transaction_depth == 0 {
+                self.saved_transaction_settings = None;
+                self.explicit_transaction = false;
+            }
+        }
+
+        if let Err(e) = &result
+            && (is_fatal(e) || self.conn.is_poisoned())
+        {
+            self.broken = true;
+        }
+
+        result
     }
 
     /// Establish a new connection with the given options
     pub async fn establish(options: &OdbcConnectOptions) -> Result<Self, Error> {
-        let conn_string = options.connection_string.clone();
         let options = options.clone();
+        let connect_options = options.clone();
+
+        let mut pre_connect_attributes = options.pre_connect_attributes.clone();
+        if let Some(token) = &options.mssql_access_token {
+            // The driver rejects a token combined with other credentials with a vague error
+            let attributes = connection_string_attributes(&options.connection_string);
+            if let Some((key, _)) = attributes.iter().find(|(key, _)| {
+                ["UID", "PWD", "Trusted_Connection", "Authentication"]
+                    .iter()
+                    .any(|credential| key.eq_ignore_ascii_case(credential))
+            }) {
+                return Err(Error::Configuration(
+                    format!("an access token cannot be combined with `{key}` in the connection string")
+                        .into(),
+                ));
+            }
+
+            let token = token.resolve().await?;
+            pre_connect_attributes.push((
+                OdbcAttribute::MSSQL_ACCESS_TOKEN,
+                encode_mssql_access_token(&token).into(),
+            ));
+        }
+
+        let (conn, dbms_info, initial_catalog) = tokio::task::spawn_blocking(move || {
+            let options = connect_options;
 
-        let shared_conn = tokio::task::spawn_blocking(move || {
-            // Get the global ODBC environment
-            let env = odbc_api::environment()
-                .map_err(|e| Error::Configuration(e.to_string().into()))?;
-            
             // Create the actual connection
-            let conn = env
-                .connect_with_connection_string(&conn_string, odbc_api::ConnectionOptions::default())
-                .map_err(|e| Error::Configuration(e.to_string().into()))?;
-            
-            // Wrap in SharedConnection for thread-safe access
-            let shared_conn = odbc_api::SharedConnection::new(std::sync::Mutex::new(conn));
-            Ok::<_, Error>(shared_conn)
+            let conn = environment::connect(&options.connection_string, &pre_connect_attributes)?;
+
+            let raw = RawConnection::of(&conn);
+            for (attribute, value) in &options.post_connect_attributes {
+                raw.set_attr(*attribute, value).map_err(|e| {
+                    Error::Configuration(
+                        format!("failed to set connection attribute {}: {e}", attribute.code)
+                            .into(),
+                    )
+                })?;
+            }
+
+            let dbms_info = OdbcDbmsInfo::query(&conn);
+            let initial_catalog = conn.current_catalog().ok();
+
+            initialize(&conn, &options, dbms_info.dialect)?;
+
+            Ok::<_, Error>((Arc::new(Mutex::new(conn)), dbms_info, initial_catalog))
         })
         .await
         .map_err(|_| Error::WorkerCrashed)??;
 
         Ok(Self {
-            conn: shared_conn,
+            conn,
             options,
+            dbms_info: Arc::new(dbms_info),
             transaction_depth: 0,
             needs_rollback: false,
+            pending_transaction_options: None,
+            explicit_transaction: false,
+            saved_transaction_settings: None,
+            broken: false,
+            initial_catalog,
+        })
+    }
+
+    /// Reset the session to the state it had right after connecting.
+    ///
+    /// Rolls back any open transaction, restores autocommit and the initial catalog, discards
+    /// session state (`sp_reset_connection` on SQL Server, `DISCARD ALL` on PostgreSQL), then
+    /// re-applies the isolation level, access mode and init statements from the connect
+    /// options. Other DBMSs keep their temporary tables and `SET` options.
+    ///
+    /// Meant for pooled connections, so the next user does not see this one's state:
+    ///
+    /// ```rust,no_run
+    /// use sqlx_odbc_core::odbc::OdbcPoolOptions;
+    ///
+    /// let pool_options = OdbcPoolOptions::new()
+    ///     .after_release(|conn, _| Box::pin(async move { conn.reset().await.map(|_| true) }));
+    /// ```
+    pub async fn reset(&mut self) -> Result<(), Error> {
+        let options = self.options.clone();
+        let dialect = self.dialect();
+        // A transaction that was already dropped is rolled back by `with_conn`, which needs the
+        // saved settings to restore them, so they are only cleared once it has run
+        let in_transaction = self.transaction_depth > 0;
+        let rollback = PendingRollback::Transaction {
+            saved: self.saved_transaction_settings,
+            explicit: self.explicit_transaction,
+        };
+        let initial_catalog = self.initial_catalog.clone();
+
+        self.with_conn("reset", move |conn| {
+            if in_transaction {
+                rollback.execute(conn, dialect).map_err(convert)?;
+            } else {
+                conn.set_autocommit(true).map_err(convert)?;
+            }
+
+            let raw = RawConnection::of(conn);
+            if let Some(catalog) = initial_catalog {
+                raw.set_attr(OdbcAttribute::CURRENT_CATALOG, &catalog.into())
+                    .map_err(convert)?;
+            }
+
+            if dialect == OdbcDialect::MsSql {
+                // Takes effect with the next round trip, i.e. before the init statements
+                raw.set_attr_u32(attr::SS_RESET_CONNECTION, 1)
+                    .map_err(convert)?;
+            } else if let Some(statement) = dialect.reset_statement() {
+                conn.execute(statement).map_err(convert)?;
+            }
+
+            initialize(conn, &options, dialect)
         })
+        .await?;
+
+        self.transaction_depth = 0;
+        self.needs_rollback = false;
+        self.saved_transaction_settings = None;
+        self.explicit_transaction = false;
+        self.pending_transaction_options = None;
+        Ok(())
     }
 
     /// Execute a raw SQL statement without returning results
@@ -95,36 +261,107 @@ impl OdbcConnection {
 
         self.with_conn("execute_raw", move |conn| {
             // Execute the statement
-            match conn.execute(&sql, (), None) {
+            match conn.execute(&sql) {
                 Ok(Some(cursor)) => {
                     // For statements that return results, we don't count rows here
                     drop(cursor);
                     Ok(OdbcQueryResult::new(0))
                 }
                 Ok(None) => Ok(OdbcQueryResult::new(0)),
-                Err(e) => Err(Error::Protocol(e.to_string())),
+                Err(e) => Err(convert(e)),
             }
         })
         .await
     }
 
-    /// Begin a transaction by disabling autocommit
-    pub(crate) async fn begin_blocking(&mut self) -> Result<(), Error> {
-        self.with_conn("begin", move |conn| {
-            conn.set_autocommit(false)
-                .map_err(|e| Error::Protocol(e.to_string()))?;
-            Ok(())
-        })
-        .await
+    /// Begin a transaction by disabling autocommit, applying `options` first
+    pub(crate) async fn begin_blocking(
+        &mut self,
+        options: Option<OdbcTransactionOptions>,
+    ) -> Result<(), Error> {
+        let dialect = self.dialect();
+
+        let saved = self
+            .with_conn("begin", move |conn| {
+                // Isolation and access mode can only change outside of a transaction
+                let saved = match options {
+                    Some(options) => Some(
+                        options
+                            .apply(conn, dialect)
+                            .map_err(convert)?,
+                    ),
+                    None => None,
+                };
+
+                if let Err(e) = conn.set_autocommit(false) {
+                    if let Some(saved) = saved {
+                        let _ = saved.restore(conn, dialect);
+                    }
+                    return Err(convert(e));
+                }
+
+                Ok(saved)
+            })
+            .await?;
+
+        if saved.is_some() {
+            self.saved_transaction_settings = saved;
+        }
+        Ok(())
+    }
+
+    /// Begin a transaction by executing a custom statement, leaving autocommit alone
+    ///
+    /// Fails with [`Error::BeginFailed`] if the statement did not open a transaction, and with
+    /// [`Error::Configuration`] if the DBMS cannot report open transactions.
+    pub(crate) async fn begin_with_statement(&mut self, statement: String) -> Result<(), Error> {
+        let dialect = self.dialect();
+        let Some(check) = dialect.open_transaction_check() else {
+            return Err(Error::Configuration(
+                format!(
+                    "{} cannot report open transactions, so custom BEGIN statements are not supported",
+                    self.dbms_info.dbms_name
+                )
+                .into(),
+            ));
+        };
+
+        let opened = self
+            .with_conn("begin", move |conn| {
+                conn.execute(&statement).map_err(convert)?;
+                has_open_transaction(conn, dialect, check)
+            })
+            .await?;
+
+        if !opened {
+            return Err(Error::BeginFailed);
+        }
+
+        self.explicit_transaction = true;
+        Ok(())
     }
 
     /// Commit the current transaction
     pub(crate) async fn commit_blocking(&mut self) -> Result<(), Error> {
+        let dialect = self.dialect();
+        let saved = self.saved_transaction_settings.take();
+        let explicit = std::mem::take(&mut self.explicit_transaction);
+
         self.with_conn("commit", move |conn| {
+            if explicit {
+                conn.execute("COMMIT").map_err(convert)?;
+                return Ok(());
+            }
+
             conn.commit()
-                .map_err(|e| Error::Protocol(e.to_string()))?;
+                .map_err(convert)?;
             conn.set_autocommit(true)
-                .map_err(|e| Error::Protocol(e.to_string()))?;
+                .map_err(convert)?;
+            if let Some(saved) = saved {
+                saved
+                    .restore(conn, dialect)
+                    .map_err(convert)?;
+            }
             Ok(())
         })
         .await
@@ -132,20 +369,210 @@ impl OdbcConnection {
 
     /// Rollback the current transaction
     pub(crate) async fn rollback_blocking(&mut self) -> Result<(), Error> {
+        let dialect = self.dialect();
+        let rollback = PendingRollback::Transaction {
+            saved: self.saved_transaction_settings.take(),
+            explicit: std::mem::take(&mut self.explicit_transaction),
+        };
+
         self.with_conn("rollback", move |conn| {
-            conn.rollback()
-                .map_err(|e| Error::Protocol(e.to_string()))?;
-            conn.set_autocommit(true)
-                .map_err(|e| Error::Protocol(e.to_string()))?;
-            Ok(())
+            rollback
+                .execute(conn, dialect)
+                .map_err(convert)
         })
         .await
     }
 
+    /// Set a connection attribute on the live connection.
+    ///
+    /// Useful for e.g. switching [`OdbcAttribute::CURRENT_CATALOG`] on a pooled connection
+    /// without reconnecting.
+    pub async fn set_attribute(
+        &mut self,
+        attribute: OdbcAttribute,
+        value: impl Into<OdbcAttributeValue>,
+    ) -> Result<(), Error> {
+        let value = value.into();
+
+        self.with_conn("set_attribute", move |conn| {
+            RawConnection::of(conn)
+                .set_attr(attribute, &value)
+                .map_err(convert)
+        })
+        .await
+    }
+
+    /// Get a connection attribute of the live connection
+    pub async fn get_attribute(&mut self, attribute: OdbcAttribute) -> Result<OdbcAttributeValue, Error> {
+        self.with_conn("get_attribute", move |conn| {
+            RawConnection::of(conn)
+                .attr(attribute)
+                .map_err(convert)
+        })
+        .await
+    }
+
+    /// Begin a transaction with the given isolation level and access mode.
+    ///
+    /// The connection's previous settings are restored when the transaction ends.
+    /// Options can only be set on the outermost transaction.
+    pub async fn begin_with_options(
+        &mut self,
+        options: OdbcTransactionOptions,
+    ) -> Result<Transaction<'_, Odbc>, Error> {
+        if self.transaction_depth > 0 {
+            return Err(Error::Configuration(
+                "transaction options can only be set on the outermost transaction".into(),
+            ));
+        }
+
+        self.pending_transaction_options = Some(options);
+        Transaction::begin(self, None).await
+    }
+
+    /// Ask the server whether it has a transaction open, next to the depth SQLx tracks.
+    ///
+    /// SQL Server reports its state through `XACT_STATE()`. On PostgreSQL a savepoint is
+    /// created and released, which fails outside of a transaction and in one that is doomed
+    /// because a statement failed. Other DBMSs report [`OdbcTransactionState::Unknown`].
+    pub async fn transaction_status(&mut self) -> Result<OdbcTransactionStatus, Error> {
+        let dialect = self.dialect();
+
+        let server = if let Some(query) = dialect.transaction_state_query() {
+            let state = self
+                .with_conn("transaction_status", move |conn| query_number(conn, query))
+                .await?;
+            match state {
+                Some(1) => OdbcTransactionState::Active,
+                Some(-1) => OdbcTransactionState::Doomed,
+                _ => OdbcTransactionState::Idle,
+            }
+        } else if let Some(check) = dialect.open_transaction_check() {
+            let result = self
+                .with_conn("transaction_status", move |conn| {
+                    has_open_transaction(conn, dialect, check)
+                })
+                .await;
+            match result {
+                Ok(true) => OdbcTransactionState::Active,
+                Ok(false) => OdbcTransactionState::Idle,
+                Err(e) if e.as_database_error().and_then(|e| e.code()).as_deref() == Some("25P02") => {
+                    OdbcTransactionState::Doomed
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            OdbcTransactionState::Unknown
+        };
+        let depth = self.transaction_depth;
+
+        Ok(OdbcTransactionStatus { depth, server })
+    }
+
+    /// Whether a fatal error (e.g. a lost network link) left the connection unusable
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
     /// Get the connection options
     pub fn options(&self) -> &OdbcConnectOptions {
         &self.options
     }
+
+    /// Get the DBMS and driver details queried when the connection was established
+    pub fn dbms_info(&self) -> &OdbcDbmsInfo {
+        &self.dbms_info
+    }
+
+    /// Get the SQL dialect of the connected DBMS
+    pub fn dialect(&self) -> OdbcDialect {
+        self.dbms_info.dialect
+    }
+}
+
+/// Run a query returning a single, possibly `NULL` number, e.g. `SELECT @@TRANCOUNT`.
+fn query_number(conn: &ConnectionHandle, sql: &str) -> Result<Option<i64>, Error> {
+    let Some(mut cursor) = conn.execute(sql).map_err(convert)? else {
+        return Err(Error::Protocol(format!("`{sql}` returned no result set")));
+    };
+
+    let mut buf = Vec::new();
+    let Some(mut row) = cursor.next_row().map_err(convert)? else {
+        return Err(Error::Protocol(format!("`{sql}` returned no rows")));
+    };
+    if !row.get_text(1, &mut buf).map_err(convert)? {
+        return Ok(None);
+    }
+
+    std::str::from_utf8(&buf)
+        .ok()
+        .and_then(|text| text.trim().parse().ok())
+        .map(Some)
+        .ok_or_else(|| Error::Protocol(format!("`{sql}` did not return a number")))
+}
+
+/// Whether the session has an open transaction.
+fn has_open_transaction(
+    conn: &ConnectionHandle,
+    dialect: OdbcDialect,
+    check: OpenTransactionCheck,
+) -> Result<bool, Error> {
+    const PROBE: &str = "_sqlx_begin_check";
+
+    match check {
+        OpenTransactionCheck::Query(query) => {
+            query_number(conn, query).map(|count| count.unwrap_or(0) > 0)
+        }
+        OpenTransactionCheck::Savepoint(no_transaction) => {
+            match conn.execute(&dialect.savepoint_statement(PROBE)) {
+                Ok(_) => {
+                    if let Some(release) = dialect.release_savepoint_statement(PROBE) {
+                        conn.execute(&release).map_err(convert)?;
+                    }
+                    Ok(true)
+                }
+                Err(e) => {
+                    let e = convert(e);
+                    match e.as_database_error().and_then(|e| e.code()) {
+                        Some(code) if code == no_transaction => Ok(false),
+                        _ => Err(e),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Apply the session settings from the connect options to a fresh or reset connection.
+fn initialize(
+    conn: &ConnectionHandle,
+    options: &OdbcConnectOptions,
+    dialect: OdbcDialect,
+) -> Result<(), Error> {
+    options
+        .transaction_options
+        .apply(conn, dialect)
+        .map_err(|e| Error::Configuration(e.to_string().into()))?;
+
+    for (i, statement) in options.init_statements.iter().enumerate() {
+        conn.execute(statement).map_err(|e| {
+            Error::Configuration(
+                format!("init statement #{} `{statement}` failed: {e}", i + 1).into(),
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+impl Drop for OdbcConnection {
+    fn drop(&mut self) {
+        // Dropping the handle would roll back an open transaction before disconnecting, which
+        // is a pointless round trip on a dead link
+        if self.broken {
+            self.conn.lock().unwrap_or_else(|e| e.into_inner()).abandon();
+        }
+    }
 }
 
 impl Connection for OdbcConnection {
@@ -154,23 +581,43 @@ impl Connection for OdbcConnection {
 
     fn close(self) -> BoxFuture<'static, Result<(), Error>> {
         Box::pin(async move {
-            // Connection will be closed when dropped
-            drop(self);
-            Ok(())
+            if !self.broken {
+                // Connection will be closed when dropped
+                drop(self);
+                return Ok(());
+            }
+
+            // Dropping abandons the handle, and disconnecting from a dead link may still block
+            // until the driver gives up
+            tokio::task::spawn_blocking(move || drop(self))
+                .await
+                .map_err(|_| Error::WorkerCrashed)
         })
     }
 
     fn close_hard(self) -> BoxFuture<'static, Result<(), Error>> {
-        Box::pin(async move {
-            drop(self);
-            Ok(())
-        })
+        // `close` already skips the rollback on broken connections
+        self.close()
     }
 
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            // Execute a simple query to check connection health
-            self.execute_raw("SELECT 1").await?;
+            // `SQL_ATTR_CONNECTION_DEAD` only reflects the last failed call, so it can confirm a
+            // dead link without a round trip but never that the link is still alive
+            let dead = self
+                .with_conn("ping", |conn| Ok(conn.is_dead().unwrap_or(false)))
+                .await?;
+
+            if dead {
+                self.broken = true;
+                return Err(Error::Protocol("ODBC ping: connection is dead".into()));
+            }
+
+            let query = match &self.options.validation_query {
+                Some(query) => query.clone(),
+                None => self.dialect().validation_query().to_string(),
+            };
+            self.execute_raw(&query).await?;
             Ok(())
         })
     }
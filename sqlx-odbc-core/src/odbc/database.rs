@@ -2,7 +2,7 @@
 
 use crate::odbc::{
     OdbcArguments, OdbcColumn, OdbcConnection, OdbcQueryResult, OdbcRow, OdbcStatement,
-    OdbcTransactionManager, OdbcTypeInfo, OdbcValue, OdbcValueRef,
+    OdbcTimestampOffset, OdbcTransactionManager, OdbcTypeInfo, OdbcValue, OdbcValueRef,
 };
 
 pub(crate) use sqlx_core::database::{Database, HasStatementCache};
@@ -37,7 +37,14 @@ impl Database for Odbc {
 
     const NAME: &'static str = "ODBC";
 
-    const URL_SCHEMES: &'static [&'static str] = &["odbc"];
+    const URL_SCHEMES: &'static [&'static str] = &[
+        "odbc",
+        "odbc+mssql",
+        "odbc+sqlserver",
+        "odbc+postgres",
+        "odbc+postgresql",
+        "odbc+mysql",
+    ];
 }
 
 impl HasStatementCache for Odbc {}
@@ -71,4 +78,8 @@ pub enum OdbcArgumentValue<'q> {
     Time(odbc_api::sys::Time),
     /// Timestamp value
     Timestamp(odbc_api::sys::Timestamp),
+    /// Timestamp with a UTC offset
+    TimestampOffset(OdbcTimestampOffset),
+    /// GUID value
+    Guid(odbc_api::sys::Guid),
 }
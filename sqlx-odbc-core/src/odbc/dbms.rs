@@ -0,0 +1,247 @@
+//! Information about the DBMS behind an ODBC connection.
+
+use crate::odbc::connection::ConnectionHandle;
+use crate::odbc::ffi::{info, RawConnection};
+use crate::odbc::OdbcIsolationLevel;
+
+/// The SQL dialect spoken by the DBMS behind a connection.
+///
+/// Detected from the DBMS name reported by the driver when the connection is established.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OdbcDialect {
+    /// Microsoft SQL Server and Azure SQL
+    MsSql,
+    /// PostgreSQL
+    PostgreSql,
+    /// MySQL and MariaDB
+    MySql,
+    /// SQLite
+    Sqlite,
+    /// Oracle Database
+    Oracle,
+    /// IBM Db2
+    Db2,
+    /// IBM Informix
+    Informix,
+    /// Any other DBMS
+    Generic,
+}
+
+impl OdbcDialect {
+    /// Map a DBMS name as reported by `SQL_DBMS_NAME` to a dialect.
+    pub fn from_dbms_name(name: &str) -> Self {
+        let name = name.trim().to_ascii_lowercase();
+
+        if name.contains("sql server") {
+            Self::MsSql
+        } else if name.starts_with("postgres") {
+            Self::PostgreSql
+        } else if name.starts_with("mysql") || name.starts_with("mariadb") {
+            Self::MySql
+        } else if name.starts_with("sqlite") {
+            Self::Sqlite
+        } else if name.starts_with("oracle") {
+            Self::Oracle
+        } else if name.starts_with("db2") {
+            Self::Db2
+        } else if name.contains("informix") || name == "ids" {
+            Self::Informix
+        } else {
+            Self::Generic
+        }
+    }
+
+    /// A cheap query that succeeds on any live connection.
+    pub fn validation_query(self) -> &'static str {
+        match self {
+            Self::Oracle => "SELECT 1 FROM DUAL",
+            Self::Db2 => "SELECT 1 FROM SYSIBM.SYSDUMMY1",
+            Self::Informix => "SELECT 1 FROM systables WHERE tabid = 1",
+            _ => "SELECT 1",
+        }
+    }
+
+    /// A statement that discards session state (temporary tables, `SET` options, prepared
+    /// statements, ...), if the dialect has one.
+    ///
+    /// SQL Server has no such statement; its session is reset through the driver instead.
+    pub fn reset_statement(self) -> Option<&'static str> {
+        match self {
+            Self::PostgreSql => Some("DISCARD ALL"),
+            _ => None,
+        }
+    }
+
+    /// How to tell whether the session has an open transaction, if the dialect can.
+    pub(crate) fn open_transaction_check(self) -> Option<OpenTransactionCheck> {
+        match self {
+            Self::MsSql => Some(OpenTransactionCheck::Query("SELECT @@TRANCOUNT")),
+            Self::PostgreSql => Some(OpenTransactionCheck::Savepoint("25P01")),
+            _ => None,
+        }
+    }
+
+    /// A query returning 1 while a committable transaction is open, -1 while a doomed one is
+    /// and 0 otherwise, if the dialect has one.
+    pub(crate) fn transaction_state_query(self) -> Option<&'static str> {
+        match self {
+            Self::MsSql => Some("SELECT XACT_STATE()"),
+            _ => None,
+        }
+    }
+
+    /// Whether the dialect has SQL savepoints.
+    pub fn supports_savepoints(self) -> bool {
+        !matches!(self, Self::Generic)
+    }
+
+    /// The statement that creates a savepoint.
+    ///
+    /// DB2 requires `ON ROLLBACK RETAIN CURSORS`, so cursors opened before the savepoint stay
+    /// open when rolling back to it.
+    pub fn savepoint_statement(self, name: &str) -> String {
+        match self {
+            Self::MsSql => format!("SAVE TRANSACTION {name}"),
+            Self::Db2 => format!("SAVEPOINT {name} ON ROLLBACK RETAIN CURSORS"),
+            _ => format!("SAVEPOINT {name}"),
+        }
+    }
+
+    /// The statement that rolls back to a savepoint.
+    pub fn rollback_to_savepoint_statement(self, name: &str) -> String {
+        match self {
+            Self::MsSql => format!("ROLLBACK TRANSACTION {name}"),
+            _ => format!("ROLLBACK TO SAVEPOINT {name}"),
+        }
+    }
+
+    /// The statement that releases a savepoint, if the dialect can release them.
+    ///
+    /// SQL Server and Oracle keep savepoints until the transaction ends.
+    pub fn release_savepoint_statement(self, name: &str) -> Option<String> {
+        match self {
+            Self::MsSql | Self::Oracle => None,
+            _ => Some(format!("RELEASE SAVEPOINT {name}")),
+        }
+    }
+}
+
+/// A check for an open transaction in the session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OpenTransactionCheck {
+    /// A query returning a positive number while a transaction is open
+    Query(&'static str),
+    /// Create and release a savepoint, which fails with the given SQLSTATE outside of a
+    /// transaction
+    Savepoint(&'static str),
+}
+
+/// Transaction support reported by `SQL_TXN_CAPABLE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OdbcTransactionCapability {
+    /// Transactions are not supported
+    #[default]
+    None,
+    /// Transactions can only contain DML; DDL causes an error
+    DmlOnly,
+    /// Transactions can contain both DML and DDL
+    All,
+    /// DDL inside a transaction commits it
+    DdlCommits,
+    /// DDL inside a transaction is ignored
+    DdlIgnored,
+}
+
+impl OdbcTransactionCapability {
+    fn from_sql(value: u16) -> Self {
+        match value {
+            1 => Self::DmlOnly,
+            2 => Self::All,
+            3 => Self::DdlCommits,
+            4 => Self::DdlIgnored,
+            _ => Self::None,
+        }
+    }
+}
+
+/// DBMS and driver details, queried once with `SQLGetInfo` when a connection is established.
+#[derive(Debug, Clone)]
+pub struct OdbcDbmsInfo {
+    /// DBMS product name (`SQL_DBMS_NAME`)
+    pub dbms_name: String,
+    /// DBMS product version (`SQL_DBMS_VER`)
+    pub dbms_version: String,
+    /// Driver file name (`SQL_DRIVER_NAME`)
+    pub driver_name: String,
+    /// Driver version (`SQL_DRIVER_VER`)
+    pub driver_version: String,
+    /// Character used to quote identifiers, if the DBMS supports quoted identifiers
+    pub identifier_quote_char: Option<char>,
+    /// Maximum identifier length, or 0 if unknown
+    pub max_identifier_len: u16,
+    /// Transaction support
+    pub transaction_capability: OdbcTransactionCapability,
+    /// Isolation level used when none is set explicitly
+    pub default_isolation_level: Option<OdbcIsolationLevel>,
+    /// Isolation levels the DBMS accepts
+    pub supported_isolation_levels: Vec<OdbcIsolationLevel>,
+    /// Dialect detected from the DBMS name
+    pub dialect: OdbcDialect,
+}
+
+impl OdbcDbmsInfo {
+    /// Query the DBMS info of a connected connection.
+    ///
+    /// Drivers are not required to answer every info type, so unanswered items fall back to
+    /// empty or unknown values instead of failing the connection.
+    pub(crate) fn query(conn: &ConnectionHandle) -> Self {
+        let raw = RawConnection::of(conn);
+
+        let dbms_name = conn.database_management_system_name().unwrap_or_default();
+        let identifier_quote_char = raw
+            .info_string(info::IDENTIFIER_QUOTE_CHAR)
+            .ok()
+            .and_then(|s| s.chars().next())
+            .filter(|c| *c != ' ');
+        let default_isolation_level = raw
+            .info_u32(info::DEFAULT_TXN_ISOLATION)
+            .ok()
+            .and_then(OdbcIsolationLevel::from_sql);
+        let supported_isolation_levels = raw
+            .info_u32(info::TXN_ISOLATION_OPTION)
+            .map(OdbcIsolationLevel::from_mask)
+            .unwrap_or_default();
+
+        Self {
+            dialect: OdbcDialect::from_dbms_name(&dbms_name),
+            dbms_name,
+            dbms_version: raw.info_string(info::DBMS_VER).unwrap_or_default(),
+            driver_name: raw.info_string(info::DRIVER_NAME).unwrap_or_default(),
+            driver_version: raw.info_string(info::DRIVER_VER).unwrap_or_default(),
+            identifier_quote_char,
+            max_identifier_len: raw.info_u16(info::MAX_IDENTIFIER_LEN).unwrap_or_default(),
+            transaction_capability: raw
+                .info_u16(info::TXN_CAPABLE)
+                .map(OdbcTransactionCapability::from_sql)
+                .unwrap_or_default(),
+            default_isolation_level,
+            supported_isolation_levels,
+        }
+    }
+
+    /// Whether the DBMS supports transactions at all.
+    pub fn supports_transactions(&self) -> bool {
+        self.transaction_capability != OdbcTransactionCapability::None
+    }
+
+    /// Whether the DBMS supports savepoints inside transactions.
+    pub fn supports_savepoints(&self) -> bool {
+        self.supports_transactions() && self.dialect.supports_savepoints()
+    }
+
+    /// Whether the DBMS accepts the given isolation level.
+    pub fn supports_isolation_level(&self, level: OdbcIsolationLevel) -> bool {
+        self.supported_isolation_levels.contains(&level)
+    }
+}
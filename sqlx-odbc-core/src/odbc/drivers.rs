@@ -0,0 +1,119 @@
+//! Discovery of installed ODBC drivers and configured data sources.
+
+use crate::odbc::Odbc;
+use sqlx_core::error::Error;
+use std::collections::HashMap;
+
+/// An ODBC driver registered with the driver manager.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdbcDriverInfo {
+    /// Name of the driver, as used in the `Driver={...}` connection string attribute
+    pub name: String,
+    /// Driver attributes by key (e.g. `Driver`, `Setup`, `FileUsage`)
+    pub attributes: HashMap<String, String>,
+}
+
+/// An ODBC data source (DSN) registered with the driver manager.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdbcDataSourceInfo {
+    /// Name of the data source, as used in the `DSN=...` connection string attribute
+    pub name: String,
+    /// Description of the data source, usually the name of its driver
+    pub description: String,
+}
+
+/// Data sources registered with the driver manager, split by scope.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OdbcDataSources {
+    /// Data sources visible only to the current user
+    pub user: Vec<OdbcDataSourceInfo>,
+    /// Data sources visible to all users of the system
+    pub system: Vec<OdbcDataSourceInfo>,
+}
+
+impl Odbc {
+    /// List the ODBC drivers installed on this system.
+    pub async fn drivers() -> Result<Vec<OdbcDriverInfo>, Error> {
+        tokio::task::spawn_blocking(|| {
+            let drivers = listing_environment()?
+                .drivers()
+                .map_err(listing_error)?
+                .into_iter()
+                .map(|driver| OdbcDriverInfo {
+                    name: driver.description,
+                    attributes: driver.attributes,
+                })
+                .collect();
+
+            Ok(drivers)
+        })
+        .await
+        .map_err(|_| Error::WorkerCrashed)?
+    }
+
+    /// List the user and system data sources configured on this system.
+    pub async fn data_sources() -> Result<OdbcDataSources, Error> {
+        tokio::task::spawn_blocking(|| {
+            let env = listing_environment()?;
+
+            let convert = |sources: Vec<odbc_api::DataSourceInfo>| {
+                sources
+                    .into_iter()
+                    .map(|source| OdbcDataSourceInfo {
+                        name: source.server_name,
+                        description: source.driver,
+                    })
+                    .collect()
+            };
+
+            let user = env.user_data_sources().map_err(listing_error)?;
+            let system = env.system_data_sources().map_err(listing_error)?;
+
+            Ok(OdbcDataSources {
+                user: convert(user),
+                system: convert(system),
+            })
+        })
+        .await
+        .map_err(|_| Error::WorkerCrashed)?
+    }
+
+    /// Look up an installed driver by name (case-insensitive).
+    ///
+    /// Returns a configuration error listing the installed drivers if `name` is not
+    /// installed, which makes this suitable for startup checks.
+    pub async fn require_driver(name: &str) -> Result<OdbcDriverInfo, Error> {
+        let name = name.trim_start_matches('{').trim_end_matches('}');
+        let drivers = Self::drivers().await?;
+
+        if let Some(driver) = drivers.iter().find(|d| d.name.eq_ignore_ascii_case(name)) {
+            return Ok(driver.clone());
+        }
+
+        let installed = if drivers.is_empty() {
+            "none".to_string()
+        } else {
+            drivers
+                .iter()
+                .map(|d| d.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        Err(Error::Configuration(
+            format!("ODBC driver `{name}` is not installed (installed drivers: {installed})").into(),
+        ))
+    }
+}
+
+/// The environment drivers and data sources are listed from.
+///
+/// Listing does not depend on the environment connections are allocated from, so this uses the
+/// one `odbc_api` keeps, which serializes the iteration for us.
+fn listing_environment() -> Result<&'static odbc_api::Environment, Error> {
+    odbc_api::environment().map_err(|e| Error::Configuration(e.to_string().into()))
+}
+
+fn listing_error(error: odbc_api::Error) -> Error {
+    Error::Protocol(error.to_string())
+}
@@ -0,0 +1,97 @@
+//! Character encoding of text read from the driver.
+
+use odbc_api::handles::SqlChar;
+use odbc_api::DataType;
+use sqlx_core::error::BoxDynError;
+
+/// A code page used to decode text returned by narrow (non-`W`) ODBC calls.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct OdbcCodePage(&'static encoding_rs::Encoding);
+
+impl OdbcCodePage {
+    /// Look up a code page by its WHATWG label (e.g. `windows-1252`, `latin1`, `shift_jis`)
+    pub fn for_label(label: &str) -> Option<Self> {
+        encoding_rs::Encoding::for_label(label.as_bytes()).map(Self)
+    }
+
+    /// The canonical name of the code page
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl std::fmt::Debug for OdbcCodePage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("OdbcCodePage").field(&self.name()).finish()
+    }
+}
+
+/// How text is read from the driver and decoded.
+///
+/// Text that is not valid in the chosen encoding fails with a decode error rather than being
+/// replaced with U+FFFD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum OdbcEncoding {
+    /// Read wide character types (`NCHAR`, `NVARCHAR`, ...) with the wide (UTF-16) API and
+    /// everything else with the narrow API as UTF-8
+    #[default]
+    Wide,
+    /// Read everything with the narrow API as UTF-8
+    Utf8,
+    /// Like [`Wide`](Self::Wide), but decode narrow text from the given code page
+    CodePage(OdbcCodePage),
+}
+
+impl OdbcEncoding {
+    /// Whether values of the given column type are read with the wide API.
+    pub(crate) fn reads_wide(&self, data_type: DataType) -> bool {
+        !matches!(self, Self::Utf8)
+            && matches!(
+                data_type,
+                DataType::WChar { .. } | DataType::WVarchar { .. } | DataType::WLongVarchar { .. }
+            )
+    }
+
+    /// Decode text returned by the narrow API
+    pub fn decode_narrow(&self, bytes: &[u8]) -> Result<String, BoxDynError> {
+        match self {
+            Self::Wide | Self::Utf8 => Ok(std::str::from_utf8(bytes)?.to_owned()),
+            Self::CodePage(code_page) => code_page
+                .0
+                .decode_without_bom_handling_and_without_replacement(bytes)
+                .map(|text| text.into_owned())
+                .ok_or_else(|| format!("invalid {} text", code_page.name()).into()),
+        }
+    }
+
+    /// Decode text returned by the wide API
+    pub(crate) fn decode_wide(units: &[u16]) -> Result<String, BoxDynError> {
+        Ok(String::from_utf16(units)?)
+    }
+
+    /// Decode a name returned by the driver (e.g. a column name)
+    ///
+    /// Names come back through the narrow or the wide API depending on how `odbc-api` was
+    /// built, so this dispatches on [`SqlChar`].
+    pub(crate) fn decode_name(&self, name: &[SqlChar]) -> Result<String, BoxDynError> {
+        SqlChar::decode(self, name)
+    }
+}
+
+/// Decoding of names in the character width `odbc-api` was built with.
+trait DecodeName: Sized {
+    fn decode(encoding: &OdbcEncoding, name: &[Self]) -> Result<String, BoxDynError>;
+}
+
+impl DecodeName for u8 {
+    fn decode(encoding: &OdbcEncoding, name: &[u8]) -> Result<String, BoxDynError> {
+        encoding.decode_narrow(name)
+    }
+}
+
+impl DecodeName for u16 {
+    fn decode(_encoding: &OdbcEncoding, name: &[u16]) -> Result<String, BoxDynError> {
+        OdbcEncoding::decode_wide(name)
+    }
+}
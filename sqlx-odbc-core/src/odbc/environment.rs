@@ -0,0 +1,254 @@
+//! The ODBC environment shared by all connections.
+
+use crate::odbc::connection::ConnectionHandle;
+use crate::odbc::ffi::RawConnection;
+use crate::odbc::{OdbcAttribute, OdbcAttributeValue};
+use odbc_api::handles::{self, SqlResult, SqlText};
+use odbc_api::sys::{AttrConnectionPooling, AttrCpMatch, AttrOdbcVersion};
+use sqlx_core::error::Error;
+use std::sync::OnceLock;
+
+/// The process-wide environment handle.
+///
+/// We allocate connections from our own environment rather than `odbc_api::environment()`
+/// so attributes can be set on a connection handle before it connects.
+pub(crate) struct Environment {
+    handle: handles::Environment,
+    /// The options the environment was created with, unless the application allocated it
+    options: Option<OdbcEnvironmentOptions>,
+}
+
+// SAFETY: ODBC environment handles are thread-safe, and we only allocate connections from it.
+unsafe impl Sync for Environment {}
+
+static ENVIRONMENT: OnceLock<Environment> = OnceLock::new();
+
+/// ODBC behavior versions an environment can declare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum OdbcVersion {
+    /// ODBC 3.0, for drivers and driver managers that reject 3.8
+    V3,
+    /// ODBC 3.8
+    #[default]
+    V3_80,
+}
+
+/// Driver-manager connection pooling schemes (`SQL_ATTR_CONNECTION_POOLING`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OdbcConnectionPooling {
+    /// One pool per driver
+    OnePerDriver,
+    /// One pool per environment
+    OnePerEnvironment,
+    /// Let pool-aware drivers manage the pool, falling back to one pool per driver
+    #[default]
+    DriverAware,
+}
+
+/// How the driver manager matches pooled connections to connection requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OdbcPoolingMatch {
+    /// Reuse only connections whose connection string and attributes match exactly
+    #[default]
+    Strict,
+    /// Reuse connections whose connection string keywords match
+    Relaxed,
+}
+
+/// Options for the process-wide ODBC environment all connections are allocated from.
+///
+/// The environment is created on the first connection with default options. To change them,
+/// call [`init`](Self::init) before connecting; afterwards it returns an error.
+///
+/// ```rust,no_run
+/// use sqlx_odbc_core::odbc::{
+///     OdbcConnectionPooling, OdbcEnvironmentOptions, OdbcPoolingMatch, OdbcVersion,
+/// };
+///
+/// # fn example() -> Result<(), sqlx_core::Error> {
+/// OdbcEnvironmentOptions::new()
+///     .odbc_version(OdbcVersion::V3)
+///     .connection_pooling(OdbcConnectionPooling::OnePerDriver)
+///     .pooling_match(OdbcPoolingMatch::Relaxed)
+///     .init()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OdbcEnvironmentOptions {
+    odbc_version: OdbcVersion,
+    connection_pooling: Option<OdbcConnectionPooling>,
+    pooling_match: OdbcPoolingMatch,
+}
+
+impl OdbcEnvironmentOptions {
+    /// Create options with ODBC 3.8 and no driver-manager pooling
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the ODBC behavior version
+    pub fn odbc_version(mut self, version: OdbcVersion) -> Self {
+        self.odbc_version = version;
+        self
+    }
+
+    /// Enable driver-manager connection pooling (`SQL_ATTR_CONNECTION_POOLING`).
+    ///
+    /// This changes process-wide state and requires a thread-safe driver. It is usually
+    /// redundant with an SQLx pool.
+    pub fn connection_pooling(mut self, scheme: OdbcConnectionPooling) -> Self {
+        self.connection_pooling = Some(scheme);
+        self
+    }
+
+    /// Set how pooled connections are matched to requests (`SQL_ATTR_CP_MATCH`). Only used
+    /// with [`connection_pooling`](Self::connection_pooling).
+    pub fn pooling_match(mut self, matching: OdbcPoolingMatch) -> Self {
+        self.pooling_match = matching;
+        self
+    }
+
+    /// The options the environment was created with.
+    ///
+    /// Returns `None` if the environment was not created yet, or was allocated by the
+    /// application with [`init_with`](Self::init_with).
+    pub fn current() -> Option<Self> {
+        ENVIRONMENT.get().and_then(|env| env.options.clone())
+    }
+
+    /// Create the environment with these options.
+    ///
+    /// Returns an error if the environment was already created, either by an earlier call or
+    /// by connecting.
+    pub fn init(self) -> Result<(), Error> {
+        ensure_uninitialized()?;
+
+        if let Some(scheme) = self.connection_pooling {
+            let scheme = match scheme {
+                OdbcConnectionPooling::OnePerDriver => AttrConnectionPooling::OnePerDriver,
+                OdbcConnectionPooling::OnePerEnvironment => AttrConnectionPooling::OnePerHenv,
+                OdbcConnectionPooling::DriverAware => AttrConnectionPooling::DriverAware,
+            };
+            // SAFETY: connections are used from the blocking thread-pool, so drivers must be
+            // thread-safe with or without pooling.
+            let result = unsafe { handles::Environment::set_connection_pooling(scheme) };
+            if let SqlResult::Error { .. } = result {
+                return Err(Error::Configuration(
+                    "failed to enable ODBC connection pooling".into(),
+                ));
+            }
+        }
+
+        let mut env = allocate(self.odbc_version)?;
+
+        if self.connection_pooling.is_some() {
+            let matching = match self.pooling_match {
+                OdbcPoolingMatch::Strict => AttrCpMatch::Strict,
+                OdbcPoolingMatch::Relaxed => AttrCpMatch::Relaxed,
+            };
+            env.set_connection_pooling_matching(matching)
+                .into_result(&env)
+                .map_err(|e| Error::Configuration(e.to_string().into()))?;
+        }
+
+        install(env, Some(self))
+    }
+
+    /// Use an environment allocated by the application.
+    ///
+    /// The environment must already have declared its ODBC version. Returns an error if the
+    /// environment was already created.
+    pub fn init_with(env: handles::Environment) -> Result<(), Error> {
+        ensure_uninitialized()?;
+        install(env, None)
+    }
+}
+
+fn ensure_uninitialized() -> Result<(), Error> {
+    if ENVIRONMENT.get().is_some() {
+        return Err(already_initialized());
+    }
+    Ok(())
+}
+
+fn install(
+    handle: handles::Environment,
+    options: Option<OdbcEnvironmentOptions>,
+) -> Result<(), Error> {
+    ENVIRONMENT
+        .set(Environment { handle, options })
+        .map_err(|_| already_initialized())
+}
+
+fn already_initialized() -> Error {
+    Error::Configuration(
+        "the ODBC environment is already initialized; configure it before the first connection"
+            .into(),
+    )
+}
+
+fn allocate(version: OdbcVersion) -> Result<handles::Environment, Error> {
+    let env = match handles::Environment::new() {
+        SqlResult::Success(env) | SqlResult::SuccessWithInfo(env) => env,
+        _ => {
+            return Err(Error::Configuration(
+                "failed to allocate ODBC environment".into(),
+            ));
+        }
+    };
+
+    let version = match version {
+        OdbcVersion::V3 => AttrOdbcVersion::Odbc3,
+        OdbcVersion::V3_80 => AttrOdbcVersion::Odbc3_80,
+    };
+    env.declare_version(version)
+        .into_result(&env)
+        .map_err(|e| Error::Configuration(e.to_string().into()))?;
+
+    Ok(env)
+}
+
+/// The process-wide environment, created with default options if [`OdbcEnvironmentOptions::init`]
+/// was not called.
+pub(crate) fn get() -> Result<&'static Environment, Error> {
+    if let Some(env) = ENVIRONMENT.get() {
+        return Ok(env);
+    }
+
+    let handle = allocate(OdbcVersion::default())?;
+    Ok(ENVIRONMENT.get_or_init(|| Environment {
+        handle,
+        options: Some(OdbcEnvironmentOptions::default()),
+    }))
+}
+
+/// Connect with a connection string, setting `pre_connect` attributes on the handle first.
+pub(crate) fn connect(
+    connection_string: &str,
+    pre_connect: &[(OdbcAttribute, OdbcAttributeValue)],
+) -> Result<ConnectionHandle, Error> {
+    let env = get()?;
+
+    let mut conn = env
+        .handle
+        .allocate_connection()
+        .into_result(&env.handle)
+        .map_err(|e| Error::Configuration(e.to_string().into()))?;
+
+    let raw = RawConnection::of_handle(&conn);
+    for (attribute, value) in pre_connect {
+        raw.set_attr(*attribute, value).map_err(|e| {
+            Error::Configuration(
+                format!("failed to set connection attribute {}: {e}", attribute.code).into(),
+            )
+        })?;
+    }
+
+    conn.connect_with_connection_string(&SqlText::new(connection_string))
+        .into_result(&conn)
+        .map_err(|e| Error::Configuration(e.to_string().into()))?;
+
+    Ok(ConnectionHandle::new(conn))
+}
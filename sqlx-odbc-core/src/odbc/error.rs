@@ -1,5 +1,6 @@
 //! ODBC error types.
 
+use crate::odbc::OdbcDialect;
 use odbc_api::Error as OdbcApiError;
 use sqlx_core::error::{BoxDynError, DatabaseError, ErrorKind};
 use std::borrow::Cow;
@@ -12,6 +13,8 @@ pub struct OdbcDatabaseError {
     pub(crate) inner: OdbcApiError,
     pub(crate) message: String,
     pub(crate) sqlstate: Option<String>,
+    pub(crate) native_error: Option<i32>,
+    pub(crate) dialect: Option<OdbcDialect>,
 }
 
 impl OdbcDatabaseError {
@@ -19,17 +22,40 @@ impl OdbcDatabaseError {
     pub fn new(error: OdbcApiError) -> Self {
         let message = error.to_string();
         let sqlstate = extract_sqlstate(&error);
+        let native_error = match &error {
+            OdbcApiError::Diagnostics { record, .. } => Some(record.native_error),
+            _ => None,
+        };
         Self {
             inner: error,
             message,
             sqlstate,
+            native_error,
+            dialect: None,
         }
     }
 
+    /// Record the dialect of the DBMS that raised the error, which gives the native error code
+    /// its meaning
+    pub fn with_dialect(mut self, dialect: OdbcDialect) -> Self {
+        self.dialect = Some(dialect);
+        self
+    }
+
     /// Get the SQLSTATE code if available
     pub fn sqlstate(&self) -> Option<&str> {
         self.sqlstate.as_deref()
     }
+
+    /// Get the DBMS-specific native error code if available (e.g. 1205 for a SQL Server deadlock)
+    pub fn native_error(&self) -> Option<i32> {
+        self.native_error
+    }
+
+    /// Get the dialect of the DBMS that raised the error, if known
+    pub fn dialect(&self) -> Option<OdbcDialect> {
+        self.dialect
+    }
 }
 
 impl Display for OdbcDatabaseError {
@@ -102,19 +128,58 @@ impl From<OdbcApiError> for OdbcDatabaseError {
 // Note: We cannot implement From<OdbcApiError> for sqlx_core::Error due to orphan rules.
 // Use OdbcDatabaseError::new(error).into() or Error::Database(Box::new(OdbcDatabaseError::new(error)))
 
+/// Convert an ODBC error, keeping errors with diagnostics as database errors so their
+/// SQLSTATE stays available.
+pub(crate) fn convert(error: OdbcApiError) -> sqlx_core::Error {
+    match error {
+        OdbcApiError::Diagnostics { .. } => {
+            sqlx_core::Error::Database(Box::new(OdbcDatabaseError::new(error)))
+        }
+        other => sqlx_core::Error::Protocol(other.to_string()),
+    }
+}
+
+/// Record the dialect of the connection an error was raised on.
+pub(crate) fn with_dialect(error: sqlx_core::Error, dialect: OdbcDialect) -> sqlx_core::Error {
+    match error {
+        sqlx_core::Error::Database(e) => match e.try_downcast::<OdbcDatabaseError>() {
+            Ok(e) => sqlx_core::Error::Database(Box::new(e.with_dialect(dialect))),
+            Err(e) => sqlx_core::Error::Database(e),
+        },
+        other => other,
+    }
+}
+
+/// Whether an error means the connection can no longer be used.
+pub(crate) fn is_fatal(error: &sqlx_core::Error) -> bool {
+    match error {
+        sqlx_core::Error::Database(e) => matches!(
+            e.code().as_deref(),
+            // Communication link failure, connection not open, failure during transaction
+            Some("08S01" | "08003" | "08007")
+        ),
+        sqlx_core::Error::Io(_) | sqlx_core::Error::WorkerCrashed => true,
+        _ => false,
+    }
+}
+
 /// Extract SQLSTATE from an ODBC error if available
 fn extract_sqlstate(error: &OdbcApiError) -> Option<String> {
+    if let OdbcApiError::Diagnostics { record, .. } = error {
+        return Some(record.state.as_str().to_string());
+    }
+
     // Try to extract SQLSTATE from the error message or structure
     // ODBC errors typically include SQLSTATE in format [SQLSTATE]
     let msg = error.to_string();
     
     // Look for pattern like [HY000] or [23505]
-    if let Some(start) = msg.find('[') {
-        if let Some(end) = msg[start..].find(']') {
-            let state = &msg[start + 1..start + end];
-            if state.len() == 5 && state.chars().all(|c| c.is_ascii_alphanumeric()) {
-                return Some(state.to_string());
-            }
+    if let Some(start) = msg.find('[')
+        && let Some(end) = msg[start..].find(']')
+    {
+        let state = &msg[start + 1..start + end];
+        if state.len() == 5 && state.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Some(state.to_string());
         }
     }
     
@@ -0,0 +1,268 @@
+//! Raw ODBC calls that `odbc_api` does not expose.
+//!
+//! Everything here operates on raw connection handles and reports failures as
+//! `odbc_api::Error`, so callers can handle them like any other ODBC error.
+
+use crate::odbc::attribute::AttributeKind;
+use crate::odbc::connection::ConnectionHandle;
+use crate::odbc::{OdbcAttribute, OdbcAttributeValue};
+use odbc_api::handles::{AnyHandle, SqlResult};
+use odbc_api::sys::{
+    HDbc, Handle, HandleType, Integer, Pointer, SmallInt, SqlReturn, USmallInt,
+};
+use std::ptr::null_mut;
+
+unsafe extern "system" {
+    // Declared here rather than used from `odbc_api::sys` because the `InfoType` enum there
+    // does not cover every info type (e.g. SQL_DRIVER_NAME).
+    fn SQLGetInfoW(
+        connection_handle: HDbc,
+        info_type: USmallInt,
+        info_value_ptr: Pointer,
+        buffer_length: SmallInt,
+        string_length_ptr: *mut SmallInt,
+    ) -> SqlReturn;
+
+    // Declared with a plain integer attribute, since the `ConnectionAttribute` enum in
+    // `odbc_api::sys` has no room for driver-specific attributes.
+    fn SQLGetConnectAttrW(
+        connection_handle: HDbc,
+        attribute: Integer,
+        value_ptr: Pointer,
+        buffer_length: Integer,
+        string_length_ptr: *mut Integer,
+    ) -> SqlReturn;
+
+    fn SQLSetConnectAttrW(
+        connection_handle: HDbc,
+        attribute: Integer,
+        value_ptr: Pointer,
+        string_length: Integer,
+    ) -> SqlReturn;
+}
+
+/// `SQLGetInfo` info types used by this crate.
+pub(crate) mod info {
+    pub const DRIVER_NAME: u16 = 6;
+    pub const DRIVER_VER: u16 = 7;
+    pub const DBMS_VER: u16 = 18;
+    pub const DEFAULT_TXN_ISOLATION: u16 = 26;
+    pub const IDENTIFIER_QUOTE_CHAR: u16 = 29;
+    pub const TXN_CAPABLE: u16 = 46;
+    pub const TXN_ISOLATION_OPTION: u16 = 72;
+    pub const MAX_IDENTIFIER_LEN: u16 = 10005;
+}
+
+/// Connection attributes used by this crate.
+pub(crate) mod attr {
+    pub const ACCESS_MODE: i32 = 101;
+    pub const TXN_ISOLATION: i32 = 108;
+    /// `SQL_COPT_SS_TXN_ISOLATION`, needed to select `SNAPSHOT` on SQL Server
+    pub const SS_TXN_ISOLATION: i32 = 1227;
+    pub const SS_RESET_CONNECTION: i32 = super::OdbcAttribute::MSSQL_RESET_CONNECTION.code;
+}
+
+/// `SQL_IS_UINTEGER`
+const IS_UINTEGER: Integer = -5;
+
+/// `SQL_IS_POINTER`
+const IS_POINTER: Integer = -4;
+
+/// `SQL_LEN_BINARY_ATTR(length)`
+fn len_binary_attr(length: usize) -> Integer {
+    -100 - length as Integer
+}
+
+/// The raw handle of a connection.
+///
+/// This only borrows the handle; it must not outlive the connection it was taken from.
+pub(crate) struct RawConnection(HDbc);
+
+unsafe impl AnyHandle for RawConnection {
+    fn as_handle(&self) -> Handle {
+        Handle(self.0.0)
+    }
+
+    fn handle_type(&self) -> HandleType {
+        HandleType::Dbc
+    }
+}
+
+impl RawConnection {
+    /// Borrow the raw handle of a connection that is not connected yet.
+    pub(crate) fn of_handle(conn: &odbc_api::handles::Connection<'_>) -> Self {
+        Self(conn.as_sys())
+    }
+
+    /// Borrow the raw handle of a connection.
+    pub(crate) fn of(conn: &ConnectionHandle) -> Self {
+        Self(conn.as_sys())
+    }
+
+    /// Get a string-valued `SQLGetInfo` item.
+    pub(crate) fn info_string(&self, info_type: u16) -> Result<String, odbc_api::Error> {
+        let mut buf: Vec<u16> = vec![0; 128];
+
+        loop {
+            let mut len_bytes: SmallInt = 0;
+            // SAFETY: the buffer length is passed in bytes and the buffer outlives the call.
+            let ret = unsafe {
+                SQLGetInfoW(
+                    self.0,
+                    info_type,
+                    buf.as_mut_ptr().cast(),
+                    (buf.len() * 2) as SmallInt,
+                    &mut len_bytes,
+                )
+            };
+            self.check(ret, "SQLGetInfoW")?;
+
+            let len = len_bytes.max(0) as usize / 2;
+            // The driver needs room for the terminating zero.
+            if len < buf.len() {
+                return Ok(String::from_utf16_lossy(&buf[..len]));
+            }
+            buf.resize(len + 1, 0);
+        }
+    }
+
+    /// Get a `SQLUSMALLINT`-valued `SQLGetInfo` item.
+    pub(crate) fn info_u16(&self, info_type: u16) -> Result<u16, odbc_api::Error> {
+        let mut value: u16 = 0;
+        // SAFETY: numeric info types write a fixed-size value and ignore the buffer length.
+        let ret = unsafe {
+            SQLGetInfoW(self.0, info_type, (&mut value as *mut u16).cast(), 0, null_mut())
+        };
+        self.check(ret, "SQLGetInfoW")?;
+        Ok(value)
+    }
+
+    /// Get a `SQLUINTEGER`-valued `SQLGetInfo` item.
+    pub(crate) fn info_u32(&self, info_type: u16) -> Result<u32, odbc_api::Error> {
+        let mut value: u32 = 0;
+        // SAFETY: numeric info types write a fixed-size value and ignore the buffer length.
+        let ret = unsafe {
+            SQLGetInfoW(self.0, info_type, (&mut value as *mut u32).cast(), 0, null_mut())
+        };
+        self.check(ret, "SQLGetInfoW")?;
+        Ok(value)
+    }
+
+    /// Get an integer-valued connection attribute.
+    pub(crate) fn attr_u32(&self, attribute: i32) -> Result<u32, odbc_api::Error> {
+        let mut value: u32 = 0;
+        // SAFETY: integer attributes write a fixed-size value and ignore the buffer length.
+        let ret = unsafe {
+            SQLGetConnectAttrW(
+                self.0,
+                attribute,
+                (&mut value as *mut u32).cast(),
+                IS_UINTEGER,
+                null_mut(),
+            )
+        };
+        self.check(ret, "SQLGetConnectAttrW")?;
+        Ok(value)
+    }
+
+    /// Set an integer-valued connection attribute.
+    pub(crate) fn set_attr_u32(&self, attribute: i32, value: u32) -> Result<(), odbc_api::Error> {
+        // SAFETY: integer attributes are passed by value in the pointer argument.
+        let ret = unsafe {
+            SQLSetConnectAttrW(self.0, attribute, value as usize as Pointer, IS_UINTEGER)
+        };
+        self.check(ret, "SQLSetConnectAttrW")
+    }
+
+    /// Get a connection attribute, interpreting its value according to the attribute's kind.
+    pub(crate) fn attr(&self, attribute: OdbcAttribute) -> Result<OdbcAttributeValue, odbc_api::Error> {
+        match attribute.kind {
+            AttributeKind::Integer => {
+                // Zeroed, so 32-bit attributes read back correctly on little-endian targets
+                let mut value: u64 = 0;
+                // SAFETY: integer attributes write at most `SQLULEN` bytes.
+                let ret = unsafe {
+                    SQLGetConnectAttrW(
+                        self.0,
+                        attribute.code,
+                        (&mut value as *mut u64).cast(),
+                        IS_UINTEGER,
+                        null_mut(),
+                    )
+                };
+                self.check(ret, "SQLGetConnectAttrW")?;
+                Ok(OdbcAttributeValue::Integer(value))
+            }
+            AttributeKind::String => {
+                let bytes = self.attr_bytes(attribute.code, 2)?;
+                let chars: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+                    .collect();
+                Ok(OdbcAttributeValue::String(String::from_utf16_lossy(&chars)))
+            }
+            AttributeKind::Binary | AttributeKind::Pointer => Ok(OdbcAttributeValue::Binary(self.attr_bytes(attribute.code, 0)?)),
+        }
+    }
+
+    /// Get a variable-length connection attribute, growing the buffer until it fits.
+    fn attr_bytes(&self, attribute: i32, terminator: usize) -> Result<Vec<u8>, odbc_api::Error> {
+        let mut buf: Vec<u8> = vec![0; 256];
+
+        loop {
+            let mut len: Integer = 0;
+            // SAFETY: the buffer length is passed in bytes and the buffer outlives the call.
+            let ret = unsafe {
+                SQLGetConnectAttrW(
+                    self.0,
+                    attribute,
+                    buf.as_mut_ptr().cast(),
+                    buf.len() as Integer,
+                    &mut len,
+                )
+            };
+            self.check(ret, "SQLGetConnectAttrW")?;
+
+            let len = len.max(0) as usize;
+            if len + terminator <= buf.len() {
+                buf.truncate(len);
+                return Ok(buf);
+            }
+            buf.resize(len + terminator, 0);
+        }
+    }
+
+    /// Set a connection attribute.
+    pub(crate) fn set_attr(&self, attribute: OdbcAttribute, value: &OdbcAttributeValue) -> Result<(), odbc_api::Error> {
+        // SAFETY: integers are passed by value in the pointer argument; strings and binary
+        // values are passed with their length in bytes and outlive the call.
+        let ret = match value {
+            OdbcAttributeValue::Integer(value) => unsafe {
+                SQLSetConnectAttrW(self.0, attribute.code, *value as usize as Pointer, IS_UINTEGER)
+            },
+            OdbcAttributeValue::String(value) => {
+                let mut buf: Vec<u16> = value.encode_utf16().chain([0]).collect();
+                let len = ((buf.len() - 1) * 2) as Integer;
+                unsafe { SQLSetConnectAttrW(self.0, attribute.code, buf.as_mut_ptr().cast(), len) }
+            }
+            OdbcAttributeValue::Binary(value) => {
+                let len = match attribute.kind {
+                    AttributeKind::Pointer => IS_POINTER,
+                    _ => len_binary_attr(value.len()),
+                };
+                unsafe { SQLSetConnectAttrW(self.0, attribute.code, value.as_ptr() as Pointer, len) }
+            }
+        };
+        self.check(ret, "SQLSetConnectAttrW")
+    }
+
+    /// Convert a return code into a result, fetching diagnostics on error.
+    fn check(&self, ret: SqlReturn, function: &'static str) -> Result<(), odbc_api::Error> {
+        let result = match ret {
+            SqlReturn::SUCCESS => SqlResult::Success(()),
+            SqlReturn::SUCCESS_WITH_INFO => SqlResult::SuccessWithInfo(()),
+            _ => SqlResult::Error { function },
+        };
+        result.into_result(self)
+    }
+}
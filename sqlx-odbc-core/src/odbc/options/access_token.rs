@@ -0,0 +1,50 @@
+//! Access-token authentication for SQL Server.
+
+use sqlx_core::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A callback returning a fresh access token, called on every connect.
+pub type OdbcAccessTokenProvider =
+    Arc<dyn Fn() -> BoxFuture<'static, Result<String, Error>> + Send + Sync>;
+
+/// Where the access token for a connection comes from.
+#[derive(Clone)]
+pub(crate) enum AccessToken {
+    Static(String),
+    Provider(OdbcAccessTokenProvider),
+}
+
+impl std::fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the token itself
+        match self {
+            Self::Static(_) => f.write_str("Static(..)"),
+            Self::Provider(_) => f.write_str("Provider(..)"),
+        }
+    }
+}
+
+impl AccessToken {
+    /// Get the token to connect with, asking the provider for a fresh one.
+    pub(crate) async fn resolve(&self) -> Result<String, Error> {
+        match self {
+            Self::Static(token) => Ok(token.clone()),
+            Self::Provider(provider) => provider().await,
+        }
+    }
+}
+
+/// Encode an access token as the `ACCESSTOKEN` struct expected by `SQL_COPT_SS_ACCESS_TOKEN`:
+/// the byte length of the token as a little-endian `u32`, followed by the token in UTF-16LE.
+pub fn encode_mssql_access_token(token: &str) -> Vec<u8> {
+    let data: Vec<u8> = token.encode_utf16().flat_map(u16::to_le_bytes).collect();
+
+    let mut encoded = Vec::with_capacity(4 + data.len());
+    encoded.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    encoded.extend_from_slice(&data);
+    encoded
+}
@@ -0,0 +1,223 @@
+//! URL parsing and formatting for ODBC connection options.
+//!
+//! See [`OdbcConnectOptions`] for the accepted URL forms.
+
+use crate::odbc::OdbcConnectOptions;
+use odbc_api::escape_attribute_value;
+use percent_encoding::percent_decode_str;
+use sqlx_core::error::Error;
+use sqlx_core::Url;
+use std::borrow::Cow;
+
+/// Query parameters that configure the options rather than the connection string.
+const DRIVER_KEY: &str = "driver";
+const INIT_STATEMENT_KEY: &str = "init_statement";
+
+/// Database-specific URL schemes of the form `odbc+<dbms>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UrlDbms {
+    Mssql,
+    Postgres,
+    Mysql,
+}
+
+impl UrlDbms {
+    fn from_scheme(scheme: &str) -> Result<Option<Self>, Error> {
+        match scheme {
+            "odbc" => Ok(None),
+            "odbc+mssql" | "odbc+sqlserver" => Ok(Some(Self::Mssql)),
+            "odbc+postgres" | "odbc+postgresql" => Ok(Some(Self::Postgres)),
+            "odbc+mysql" => Ok(Some(Self::Mysql)),
+            other => Err(Error::Configuration(
+                format!("unsupported ODBC URL scheme `{other}`").into(),
+            )),
+        }
+    }
+
+    /// Guess the DBMS from a driver name, e.g. `ODBC Driver 18 for SQL Server`.
+    fn from_driver(driver: &str) -> Option<Self> {
+        let driver = driver.to_ascii_lowercase();
+        if driver.contains("sql server") {
+            Some(Self::Mssql)
+        } else if driver.contains("postgres") {
+            Some(Self::Postgres)
+        } else if driver.contains("mysql") || driver.contains("mariadb") {
+            Some(Self::Mysql)
+        } else {
+            None
+        }
+    }
+
+    fn default_driver(self) -> &'static str {
+        match self {
+            Self::Mssql => "ODBC Driver 18 for SQL Server",
+            Self::Postgres => "PostgreSQL Unicode",
+            Self::Mysql => "MySQL ODBC 8.0 Unicode Driver",
+        }
+    }
+}
+
+/// Parse an `odbc:` or `odbc+<dbms>:` URL into connection options.
+pub(super) fn parse(url: &Url) -> Result<OdbcConnectOptions, Error> {
+    let dbms = UrlDbms::from_scheme(url.scheme())?;
+
+    if url.cannot_be_a_base() {
+        if dbms.is_some() {
+            return Err(Error::Configuration(
+                format!("`{}:` URLs must have the form `{}://host/database`", url.scheme(), url.scheme()).into(),
+            ));
+        }
+
+        // Everything after the scheme is the connection string, which may well contain `?`
+        // or `#` (e.g. in a password)
+        return Ok(OdbcConnectOptions::new(raw_connection_string(url.as_str())));
+    }
+
+    let host = decode(url.host_str().unwrap_or_default())?;
+
+    // Earlier versions accepted `odbc://<connection string>`; keep accepting it, merging in
+    // credentials and query attributes like the other forms.
+    if dbms.is_none() && host.contains('=') {
+        if url.query_pairs().any(|(key, _)| key.eq_ignore_ascii_case(DRIVER_KEY)) {
+            return Err(Error::Configuration(
+                "the `driver` query parameter cannot be combined with a connection string in the host".into(),
+            ));
+        }
+
+        let mut conn_string = host.into_owned();
+        conn_string.push_str(&decode(url.path())?);
+
+        let mut attributes = Vec::new();
+        if !url.username().is_empty() {
+            attributes.push(("UID", escape(&decode(url.username())?)));
+        }
+        if let Some(password) = url.password() {
+            attributes.push(("PWD", escape(&decode(password)?)));
+        }
+        for (key, value) in attributes {
+            push_attribute(&mut conn_string, key, &value);
+        }
+
+        append_query_attributes(&mut conn_string, url)?;
+        return Ok(OdbcConnectOptions::new(conn_string).init_statements(init_statements(url)));
+    }
+
+    let driver = url
+        .query_pairs()
+        .find(|(key, _)| key.eq_ignore_ascii_case(DRIVER_KEY))
+        .map(|(_, value)| value.into_owned())
+        .or_else(|| dbms.map(|dbms| dbms.default_driver().to_owned()));
+
+    let mut attributes = Vec::new();
+
+    match driver {
+        Some(driver) => {
+            let family = dbms.or_else(|| UrlDbms::from_driver(&driver));
+            attributes.push(("Driver", braced(&driver)));
+
+            if !host.is_empty() {
+                match (family, url.port()) {
+                    // SQL Server drivers ignore `Port`; the port goes after the host
+                    (Some(UrlDbms::Mssql), Some(port)) => {
+                        attributes.push(("Server", escape(&format!("{host},{port}"))));
+                    }
+                    (_, Some(port)) => {
+                        attributes.push(("Server", escape(&host)));
+                        attributes.push(("Port", port.to_string()));
+                    }
+                    (_, None) => attributes.push(("Server", escape(&host))),
+                }
+            }
+        }
+        None if host.starts_with('{') => attributes.push(("Driver", host.into_owned())),
+        None if !host.is_empty() => attributes.push(("DSN", escape(&host))),
+        None => {}
+    }
+
+    let database = decode(url.path().trim_start_matches('/'))?;
+    if !database.is_empty() {
+        attributes.push(("Database", escape(&database)));
+    }
+
+    if !url.username().is_empty() {
+        attributes.push(("UID", escape(&decode(url.username())?)));
+    }
+
+    if let Some(password) = url.password() {
+        attributes.push(("PWD", escape(&decode(password)?)));
+    }
+
+    let mut conn_string = attributes
+        .into_iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(";");
+
+    append_query_attributes(&mut conn_string, url)?;
+
+    Ok(OdbcConnectOptions::new(conn_string).init_statements(init_statements(url)))
+}
+
+/// Format connection options as an `odbc:` URL.
+pub(super) fn build(options: &OdbcConnectOptions) -> Url {
+    Url::parse(&format!("odbc:{}", options.connection_string))
+        .unwrap_or_else(|_| Url::parse("odbc:").unwrap())
+}
+
+/// The connection string of an `odbc:<connection string>` URL, taken verbatim.
+pub(super) fn raw_connection_string(url: &str) -> &str {
+    &url["odbc:".len()..]
+}
+
+/// Collect the `init_statement` query parameters, in order.
+fn init_statements(url: &Url) -> Vec<String> {
+    url.query_pairs()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(INIT_STATEMENT_KEY))
+        .map(|(_, value)| value.into_owned())
+        .collect()
+}
+
+/// Append every query parameter that doesn't configure the options as a connection string
+/// attribute.
+fn append_query_attributes(conn_string: &mut String, url: &Url) -> Result<(), Error> {
+    for (key, value) in url.query_pairs() {
+        if key.eq_ignore_ascii_case(DRIVER_KEY) || key.eq_ignore_ascii_case(INIT_STATEMENT_KEY) {
+            continue;
+        }
+
+        if key.is_empty() || key.contains(['=', ';']) {
+            return Err(Error::Configuration(
+                format!("invalid connection string attribute `{key}` in ODBC URL").into(),
+            ));
+        }
+
+        push_attribute(conn_string, &key, &escape(&value));
+    }
+
+    Ok(())
+}
+
+/// Append an already escaped `key=value` attribute to a connection string.
+fn push_attribute(conn_string: &mut String, key: &str, value: &str) {
+    if !conn_string.is_empty() && !conn_string.ends_with(';') {
+        conn_string.push(';');
+    }
+    conn_string.push_str(key);
+    conn_string.push('=');
+    conn_string.push_str(value);
+}
+
+fn decode(s: &str) -> Result<Cow<'_, str>, Error> {
+    percent_decode_str(s)
+        .decode_utf8()
+        .map_err(|e| Error::Configuration(format!("invalid percent-encoding in ODBC URL: {e}").into()))
+}
+
+fn escape(value: &str) -> String {
+    escape_attribute_value(value).into_owned()
+}
+
+/// Wrap a value in braces, doubling any closing braces, as drivers expect for `Driver`.
+fn braced(value: &str) -> String {
+    format!("{{{}}}", value.replace('}', "}}"))
+}
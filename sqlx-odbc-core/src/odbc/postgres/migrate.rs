@@ -27,11 +27,10 @@ use odbc_api::Cursor;
 use sqlx_core::migrate::MigrateError;
 use sqlx_core::migrate::{AppliedMigration, Migration, Migrator};
 
-use crate::odbc::connection::OdbcConnectionInner;
-use crate::odbc::{Odbc, OdbcConnectOptions, OdbcConnection};
+use crate::odbc::connection::ConnectionHandle;
+use crate::odbc::{Odbc, OdbcConnectOptions, OdbcConnection, OdbcEncoding};
 use sqlx_core::connection::ConnectOptions;
 use sqlx_core::error::Error;
-use std::sync::{Arc, Mutex};
 
 /// Default migrations table name
 const MIGRATIONS_TABLE: &str = "_sqlx_migrations";
@@ -579,14 +578,14 @@ async fn revert_migration(
 
 /// Get the current database name
 async fn current_database(conn: &mut OdbcConnection) -> Result<String, MigrateError> {
-    let inner = conn.inner.clone();
+    let encoding = conn.options.encoding;
 
-    let result = tokio::task::spawn_blocking(move || {
-        execute_scalar_string_sync(inner, "SELECT current_database()".to_string())
-    })
-    .await
-    .map_err(|_| MigrateError::Source(Error::WorkerCrashed.into()))?
-    .map_err(|e| MigrateError::Source(e.into()))?;
+    let result = conn
+        .with_conn("migrate", move |odbc_conn| {
+            execute_scalar_string_sync(odbc_conn, "SELECT current_database()", encoding)
+        })
+        .await
+        .map_err(|e| MigrateError::Source(e.into()))?;
 
     Ok(result.unwrap_or_else(|| "postgres".to_string()))
 }
@@ -600,20 +599,11 @@ fn generate_lock_id(database_name: &str) -> i64 {
 
 /// Execute a query and return a boolean result
 async fn execute_scalar_bool(conn: &mut OdbcConnection, sql: &str) -> Result<bool, Error> {
-    let inner = conn.inner.clone();
+    let encoding = conn.options.encoding;
     let sql = sql.to_string();
 
-    tokio::task::spawn_blocking(move || {
-        let inner = inner.lock().map_err(|_| {
-            Error::Protocol("Failed to lock ODBC connection".into())
-        })?;
-
-        let odbc_conn = inner
-            .env
-            .connect_with_connection_string(&inner.connection_string, odbc_api::ConnectionOptions::default())
-            .map_err(|e| Error::Protocol(e.to_string()))?;
-
-        match odbc_conn.execute(&sql, (), None) {
+    conn.with_conn("migrate", move |odbc_conn| {
+        match odbc_conn.execute(&sql) {
             Ok(Some(mut cursor)) => {
                 if let Some(mut row) = cursor.next_row().map_err(|e| Error::Protocol(e.to_string()))? {
                     let mut buf = Vec::new();
@@ -622,7 +612,7 @@ async fn execute_scalar_bool(conn: &mut OdbcConnection, sql: &str) -> Result<boo
                         .map_err(|e| Error::Protocol(e.to_string()))?;
 
                     if has_value {
-                        let s = String::from_utf8_lossy(&buf);
+                        let s = encoding.decode_narrow(&buf).map_err(Error::Decode)?;
                         let s = s.trim().to_lowercase();
                         Ok(s == "1" || s == "t" || s == "true")
                     } else {
@@ -637,25 +627,15 @@ async fn execute_scalar_bool(conn: &mut OdbcConnection, sql: &str) -> Result<boo
         }
     })
     .await
-    .map_err(|_| Error::WorkerCrashed)?
 }
 
 /// Execute a query and return an optional i64 result
 async fn execute_scalar_i64(conn: &mut OdbcConnection, sql: &str) -> Result<Option<i64>, MigrateError> {
-    let inner = conn.inner.clone();
+    let encoding = conn.options.encoding;
     let sql = sql.to_string();
 
-    let result = tokio::task::spawn_blocking(move || {
-        let inner = inner.lock().map_err(|_| {
-            Error::Protocol("Failed to lock ODBC connection".into())
-        })?;
-
-        let odbc_conn = inner
-            .env
-            .connect_with_connection_string(&inner.connection_string, odbc_api::ConnectionOptions::default())
-            .map_err(|e| Error::Protocol(e.to_string()))?;
-
-        match odbc_conn.execute(&sql, (), None) {
+    let result = conn.with_conn("migrate", move |odbc_conn| {
+        match odbc_conn.execute(&sql) {
             Ok(Some(mut cursor)) => {
                 if let Some(mut row) = cursor.next_row().map_err(|e| Error::Protocol(e.to_string()))? {
                     let mut buf = Vec::new();
@@ -664,7 +644,7 @@ async fn execute_scalar_i64(conn: &mut OdbcConnection, sql: &str) -> Result<Opti
                         .map_err(|e| Error::Protocol(e.to_string()))?;
 
                     if has_value {
-                        let s = String::from_utf8_lossy(&buf);
+                        let s = encoding.decode_narrow(&buf).map_err(Error::Decode)?;
                         match s.trim().parse::<i64>() {
                             Ok(v) => Ok(Some(v)),
                             Err(_) => Ok(None),
@@ -681,7 +661,6 @@ async fn execute_scalar_i64(conn: &mut OdbcConnection, sql: &str) -> Result<Opti
         }
     })
     .await
-    .map_err(|_| MigrateError::Source(Error::WorkerCrashed.into()))?
     .map_err(|e| MigrateError::Source(e.into()))?;
 
     Ok(result)
@@ -692,20 +671,11 @@ async fn execute_query_migrations(
     conn: &mut OdbcConnection,
     sql: &str,
 ) -> Result<Vec<(i64, Vec<u8>)>, MigrateError> {
-    let inner = conn.inner.clone();
+    let encoding = conn.options.encoding;
     let sql = sql.to_string();
 
-    let result = tokio::task::spawn_blocking(move || {
-        let inner = inner.lock().map_err(|_| {
-            Error::Protocol("Failed to lock ODBC connection".into())
-        })?;
-
-        let odbc_conn = inner
-            .env
-            .connect_with_connection_string(&inner.connection_string, odbc_api::ConnectionOptions::default())
-            .map_err(|e| Error::Protocol(e.to_string()))?;
-
-        match odbc_conn.execute(&sql, (), None) {
+    let result = conn.with_conn("migrate", move |odbc_conn| {
+        match odbc_conn.execute(&sql) {
             Ok(Some(mut cursor)) => {
                 let mut rows = Vec::new();
 
@@ -716,7 +686,9 @@ async fn execute_query_migrations(
                         .map_err(|e| Error::Protocol(e.to_string()))?;
 
                     let version: i64 = if has_version {
-                        String::from_utf8_lossy(&version_buf)
+                        encoding
+                            .decode_narrow(&version_buf)
+                            .map_err(Error::Decode)?
                             .trim()
                             .parse()
                             .unwrap_or(0)
@@ -741,7 +713,6 @@ async fn execute_query_migrations(
         }
     })
     .await
-    .map_err(|_| MigrateError::Source(Error::WorkerCrashed.into()))?
     .map_err(|e| MigrateError::Source(e.into()))?;
 
     Ok(result)
@@ -749,19 +720,11 @@ async fn execute_query_migrations(
 
 /// Synchronous helper to execute a scalar string query
 fn execute_scalar_string_sync(
-    inner: Arc<Mutex<OdbcConnectionInner>>,
-    sql: String,
+    odbc_conn: &ConnectionHandle,
+    sql: &str,
+    encoding: OdbcEncoding,
 ) -> Result<Option<String>, Error> {
-    let inner = inner.lock().map_err(|_| {
-        Error::Protocol("Failed to lock ODBC connection".into())
-    })?;
-
-    let odbc_conn = inner
-        .env
-        .connect_with_connection_string(&inner.connection_string, odbc_api::ConnectionOptions::default())
-        .map_err(|e| Error::Protocol(e.to_string()))?;
-
-    match odbc_conn.execute(&sql, (), None) {
+    match odbc_conn.execute(sql) {
         Ok(Some(mut cursor)) => {
             if let Some(mut row) = cursor.next_row().map_err(|e| Error::Protocol(e.to_string()))? {
                 let mut buf = Vec::new();
@@ -770,7 +733,7 @@ fn execute_scalar_string_sync(
                     .map_err(|e| Error::Protocol(e.to_string()))?;
 
                 if has_value {
-                    Ok(Some(String::from_utf8_lossy(&buf).to_string()))
+                    Ok(Some(encoding.decode_narrow(&buf).map_err(Error::Decode)?))
                 } else {
                     Ok(None)
                 }
@@ -0,0 +1,179 @@
+//! Retrying transactions that failed with transient errors.
+
+use crate::odbc::{Odbc, OdbcDatabaseError, OdbcDialect, OdbcPool};
+use sqlx_core::error::Error;
+use sqlx_core::transaction::Transaction;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// SQLSTATEs worth retrying: serialization failures and deadlocks, lock timeouts, and lost
+/// connections.
+const TRANSIENT_SQLSTATES: &[&str] = &[
+    "40001", "40P01", "55P03", "HYT00", "08S01", "08003", "08007",
+];
+
+/// Native error codes worth retrying, with the dialect that defines them: SQL Server deadlock
+/// (1205) and lock timeout (1222), MySQL deadlock (1213) and lock wait timeout (1205).
+const TRANSIENT_NATIVE_ERRORS: &[(OdbcDialect, i32)] = &[
+    (OdbcDialect::MsSql, 1205),
+    (OdbcDialect::MsSql, 1222),
+    (OdbcDialect::MySql, 1213),
+    (OdbcDialect::MySql, 1205),
+];
+
+/// When and how often [`retry_transaction`] re-runs a transaction.
+#[derive(Clone)]
+pub struct OdbcRetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    classifier: Arc<dyn Fn(&Error) -> bool + Send + Sync>,
+}
+
+impl std::fmt::Debug for OdbcRetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OdbcRetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .finish()
+    }
+}
+
+impl Default for OdbcRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(2),
+            classifier: Arc::new(Self::is_transient),
+        }
+    }
+}
+
+impl OdbcRetryPolicy {
+    /// Create a policy with 3 attempts, exponential backoff from 50ms up to 2s, and
+    /// [`is_transient`](Self::is_transient) classification
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of attempts, including the first
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry and the cap for the doubling delays after it
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the predicate deciding which errors are retried
+    pub fn retry_if<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.classifier = Arc::new(classifier);
+        self
+    }
+
+    /// Whether the policy retries the given error
+    pub fn should_retry(&self, error: &Error) -> bool {
+        (self.classifier)(error)
+    }
+
+    /// The delay before the given retry (1 for the first retry)
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// The default classification: deadlocks, serialization failures, lock timeouts and lost
+    /// connections, by SQLSTATE or by native error code of the DBMS the error came from.
+    pub fn is_transient(error: &Error) -> bool {
+        let Some(error) = error
+            .as_database_error()
+            .and_then(|e| e.try_downcast_ref::<OdbcDatabaseError>())
+        else {
+            return matches!(error, Error::Io(_));
+        };
+
+        error
+            .sqlstate()
+            .is_some_and(|state| TRANSIENT_SQLSTATES.contains(&state))
+            || error
+                .dialect()
+                .zip(error.native_error())
+                .is_some_and(|code| TRANSIENT_NATIVE_ERRORS.contains(&code))
+    }
+}
+
+/// Run `f` in a transaction, re-running it in a fresh transaction when it fails with an error
+/// the policy retries.
+///
+/// The transaction is committed if `f` succeeds and rolled back if it fails. Each attempt
+/// acquires its own connection from the pool, so a broken connection is not reused.
+///
+/// ```rust,no_run
+/// use sqlx_odbc_core::odbc::{retry_transaction, OdbcPool, OdbcRetryPolicy};
+/// use sqlx_core::executor::Executor;
+///
+/// # async fn example(pool: &OdbcPool) -> Result<(), sqlx_core::Error> {
+/// retry_transaction(pool, &OdbcRetryPolicy::new(), |tx| {
+///     Box::pin(async move {
+///         tx.execute("UPDATE accounts SET balance = balance - 10 WHERE id = 1").await?;
+///         tx.execute("UPDATE accounts SET balance = balance + 10 WHERE id = 2").await?;
+///         Ok(())
+///     })
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn retry_transaction<T, F>(
+    pool: &OdbcPool,
+    policy: &OdbcRetryPolicy,
+    mut f: F,
+) -> Result<T, Error>
+where
+    F: for<'t> FnMut(&'t mut Transaction<'static, Odbc>) -> BoxFuture<'t, Result<T, Error>>,
+    T: Send,
+{
+    let mut attempt = 1;
+
+    loop {
+        let result = async {
+            let mut tx = pool.begin().await?;
+            match f(&mut tx).await {
+                Ok(value) => {
+                    tx.commit().await?;
+                    Ok(value)
+                }
+                Err(e) => {
+                    // The error from `f` is the one worth reporting
+                    let _ = tx.rollback().await;
+                    Err(e)
+                }
+            }
+        }
+        .await;
+
+        match result {
+            Err(e) if attempt < policy.max_attempts && policy.should_retry(&e) => {
+                log::debug!("retrying transaction after attempt {attempt} failed: {e}");
+                tokio::time::sleep(policy.delay(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
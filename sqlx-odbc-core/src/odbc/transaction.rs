@@ -1,12 +1,250 @@
 //! ODBC transaction manager.
 
-use crate::odbc::{Odbc, OdbcConnection};
+use crate::odbc::connection::ConnectionHandle;
+use crate::odbc::ffi::{attr, RawConnection};
+use crate::odbc::{Odbc, OdbcConnection, OdbcDialect};
 use sqlx_core::error::Error;
 use sqlx_core::transaction::TransactionManager;
 use std::borrow::Cow;
 use std::future::Future;
 use std::pin::Pin;
 
+/// Transaction isolation levels, as set through `SQL_ATTR_TXN_ISOLATION`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OdbcIsolationLevel {
+    /// `READ UNCOMMITTED`
+    ReadUncommitted,
+    /// `READ COMMITTED`
+    ReadCommitted,
+    /// `REPEATABLE READ`
+    RepeatableRead,
+    /// `SERIALIZABLE`
+    Serializable,
+    /// `SNAPSHOT` (SQL Server only)
+    Snapshot,
+}
+
+impl OdbcIsolationLevel {
+    const ALL: [Self; 5] = [
+        Self::ReadUncommitted,
+        Self::ReadCommitted,
+        Self::RepeatableRead,
+        Self::Serializable,
+        Self::Snapshot,
+    ];
+
+    /// The `SQL_TXN_*` bitmask value of this level.
+    pub(crate) const fn to_sql(self) -> u32 {
+        match self {
+            Self::ReadUncommitted => 0x01,
+            Self::ReadCommitted => 0x02,
+            Self::RepeatableRead => 0x04,
+            Self::Serializable => 0x08,
+            // SQL_TXN_SS_SNAPSHOT from msodbcsql.h
+            Self::Snapshot => 0x20,
+        }
+    }
+
+    /// Map a single `SQL_TXN_*` value to a level.
+    pub(crate) fn from_sql(value: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.to_sql() == value)
+    }
+
+    /// Map a `SQL_TXN_*` bitmask to the levels it contains.
+    pub(crate) fn from_mask(mask: u32) -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|level| mask & level.to_sql() != 0)
+            .collect()
+    }
+}
+
+/// Whether the server has a transaction open, see [`OdbcConnection::transaction_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OdbcTransactionState {
+    /// No transaction is open
+    Idle,
+    /// A transaction is open and can be committed
+    Active,
+    /// A transaction is open but can only be rolled back, e.g. after an error under
+    /// `XACT_ABORT` on SQL Server or any error on PostgreSQL
+    Doomed,
+    /// The DBMS cannot report its transaction state
+    Unknown,
+}
+
+/// Client- and server-side transaction state of a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OdbcTransactionStatus {
+    /// Transaction depth tracked by SQLx
+    pub depth: usize,
+    /// Transaction state reported by the server
+    pub server: OdbcTransactionState,
+}
+
+impl OdbcTransactionStatus {
+    /// Whether SQLx thinks a transaction is open that the server has already ended, e.g.
+    /// because SQL Server rolled it back after a severe error.
+    pub fn is_out_of_sync(&self) -> bool {
+        self.depth > 0 && self.server == OdbcTransactionState::Idle
+    }
+
+    /// Whether the open transaction can still be committed.
+    pub fn is_committable(&self) -> bool {
+        self.depth > 0 && self.server == OdbcTransactionState::Active
+    }
+}
+
+/// Options for a single transaction, see [`OdbcConnection::begin_with_options`].
+///
+/// Unset options keep the connection's current setting. Settings changed for the
+/// transaction are restored when it ends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OdbcTransactionOptions {
+    pub(crate) isolation_level: Option<OdbcIsolationLevel>,
+    pub(crate) read_only: Option<bool>,
+}
+
+impl OdbcTransactionOptions {
+    /// Create options that keep the connection's current settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the isolation level
+    pub fn isolation_level(mut self, level: OdbcIsolationLevel) -> Self {
+        self.isolation_level = Some(level);
+        self
+    }
+
+    /// Set whether the transaction is read-only.
+    ///
+    /// Some drivers (e.g. SQL Server) only treat this as a hint.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
+    /// Apply the options to a connection, returning the settings they replaced.
+    pub(crate) fn apply(
+        &self,
+        conn: &ConnectionHandle,
+        dialect: OdbcDialect,
+    ) -> Result<SavedTransactionSettings, odbc_api::Error> {
+        let raw = RawConnection::of(conn);
+        let mut saved = SavedTransactionSettings::default();
+
+        if let Some(level) = self.isolation_level {
+            let previous = raw.attr_u32(attr::TXN_ISOLATION)?;
+            set_isolation(&raw, dialect, level.to_sql())?;
+            saved.isolation = Some(previous);
+        }
+
+        if let Some(read_only) = self.read_only {
+            let previous = raw.attr_u32(attr::ACCESS_MODE)?;
+            raw.set_attr_u32(attr::ACCESS_MODE, u32::from(read_only))?;
+            saved.access_mode = Some(previous);
+        }
+
+        Ok(saved)
+    }
+}
+
+/// Connection settings replaced for the duration of a transaction.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SavedTransactionSettings {
+    isolation: Option<u32>,
+    access_mode: Option<u32>,
+}
+
+impl SavedTransactionSettings {
+    /// Put the replaced settings back. Must be called outside of a transaction.
+    pub(crate) fn restore(
+        self,
+        conn: &ConnectionHandle,
+        dialect: OdbcDialect,
+    ) -> Result<(), odbc_api::Error> {
+        let raw = RawConnection::of(conn);
+
+        if let Some(isolation) = self.isolation {
+            set_isolation(&raw, dialect, isolation)?;
+        }
+        if let Some(access_mode) = self.access_mode {
+            raw.set_attr_u32(attr::ACCESS_MODE, access_mode)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A rollback queued by [`TransactionManager::start_rollback`], run before the connection's
+/// next operation.
+#[derive(Debug, Clone)]
+pub(crate) enum PendingRollback {
+    /// Roll back the whole transaction, then restore autocommit and the replaced settings.
+    ///
+    /// `explicit` transactions were started by a custom statement and are rolled back with
+    /// SQL, since `SQLEndTran` does nothing while autocommit is on.
+    Transaction {
+        saved: Option<SavedTransactionSettings>,
+        explicit: bool,
+    },
+    /// Roll back to the savepoint that started a nested transaction
+    Savepoint(String),
+}
+
+impl PendingRollback {
+    /// The rollback a connection still owes, if any.
+    pub(crate) fn of(conn: &OdbcConnection) -> Option<Self> {
+        if !conn.needs_rollback {
+            return None;
+        }
+
+        Some(match conn.transaction_depth {
+            0 => Self::Transaction {
+                saved: conn.saved_transaction_settings,
+                explicit: conn.explicit_transaction,
+            },
+            depth => Self::Savepoint(savepoint_name(depth)),
+        })
+    }
+
+    pub(crate) fn execute(
+        self,
+        conn: &ConnectionHandle,
+        dialect: OdbcDialect,
+    ) -> Result<(), odbc_api::Error> {
+        match self {
+            Self::Transaction { saved, explicit } => {
+                if explicit {
+                    conn.execute("ROLLBACK")?;
+                } else {
+                    conn.rollback()?;
+                }
+                conn.set_autocommit(true)?;
+                if let Some(saved) = saved {
+                    saved.restore(conn, dialect)?;
+                }
+            }
+            Self::Savepoint(name) => {
+                conn.execute(&dialect.rollback_to_savepoint_statement(&name))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn set_isolation(raw: &RawConnection, dialect: OdbcDialect, value: u32) -> Result<(), odbc_api::Error> {
+    // SQL Server only accepts SNAPSHOT through its own attribute
+    if dialect == OdbcDialect::MsSql && value == OdbcIsolationLevel::Snapshot.to_sql() {
+        raw.set_attr_u32(attr::SS_TXN_ISOLATION, value)
+    } else {
+        raw.set_attr_u32(attr::TXN_ISOLATION, value)
+    }
+}
+
 /// Transaction manager for ODBC connections.
 pub struct OdbcTransactionManager;
 
@@ -15,10 +253,33 @@ type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 impl TransactionManager for OdbcTransactionManager {
     type Database = Odbc;
 
-    fn begin<'c>(conn: &'c mut OdbcConnection, _statement: Option<Cow<'static, str>>) -> BoxFuture<'c, Result<(), Error>> {
+    fn begin<'c>(conn: &'c mut OdbcConnection, statement: Option<Cow<'static, str>>) -> BoxFuture<'c, Result<(), Error>> {
         Box::pin(async move {
-            // Use ODBC's native autocommit control for reliable transaction management
-            conn.begin_blocking().await?;
+            let depth = conn.transaction_depth;
+
+            if depth == 0 {
+                match statement {
+                    Some(statement) => conn.begin_with_statement(statement.into_owned()).await?,
+                    None => {
+                        let options = conn.pending_transaction_options.take();
+
+                        // Use ODBC's native autocommit control for reliable transaction management
+                        conn.begin_blocking(options).await?;
+                    }
+                }
+            } else if statement.is_some() {
+                return Err(Error::InvalidSavePointStatement);
+            } else {
+                if !conn.dbms_info.supports_savepoints() {
+                    return Err(Error::Configuration(
+                        format!("{} does not support nested transactions", conn.dbms_info.dbms_name).into(),
+                    ));
+                }
+
+                let sql = conn.dialect().savepoint_statement(&savepoint_name(depth));
+                conn.execute_raw(&sql).await?;
+            }
+
             conn.transaction_depth += 1;
             Ok(())
         })
@@ -26,8 +287,18 @@ impl TransactionManager for OdbcTransactionManager {
 
     fn commit(conn: &mut OdbcConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            if conn.transaction_depth > 0 {
+            let depth = conn.transaction_depth;
+
+            if depth == 1 {
                 conn.commit_blocking().await?;
+            } else if depth > 1 {
+                let name = savepoint_name(depth - 1);
+                if let Some(sql) = conn.dialect().release_savepoint_statement(&name) {
+                    conn.execute_raw(&sql).await?;
+                }
+            }
+
+            if depth > 0 {
                 conn.transaction_depth -= 1;
             }
             Ok(())
@@ -36,8 +307,18 @@ impl TransactionManager for OdbcTransactionManager {
 
     fn rollback(conn: &mut OdbcConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            if conn.transaction_depth > 0 {
+            let depth = conn.transaction_depth;
+
+            if depth == 1 {
                 conn.rollback_blocking().await?;
+            } else if depth > 1 {
+                let sql = conn
+                    .dialect()
+                    .rollback_to_savepoint_statement(&savepoint_name(depth - 1));
+                conn.execute_raw(&sql).await?;
+            }
+
+            if depth > 0 {
                 conn.transaction_depth -= 1;
             }
             Ok(())
@@ -45,11 +326,20 @@ impl TransactionManager for OdbcTransactionManager {
     }
 
     fn start_rollback(conn: &mut OdbcConnection) {
-        // Mark that we need to rollback - actual rollback happens in rollback()
-        conn.needs_rollback = true;
+        // This can't block, so the rollback runs before the connection's next operation
+        // (at the latest the ping when it is returned to the pool)
+        if conn.transaction_depth > 0 {
+            conn.transaction_depth -= 1;
+            conn.needs_rollback = true;
+        }
     }
 
     fn get_transaction_depth(conn: &OdbcConnection) -> usize {
         conn.transaction_depth
     }
 }
+
+/// The name of the savepoint that starts the transaction at `depth` (counting from 0).
+fn savepoint_name(depth: usize) -> String {
+    format!("_sqlx_savepoint_{depth}")
+}
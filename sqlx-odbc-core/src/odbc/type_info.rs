@@ -1,9 +1,13 @@
 //! ODBC type information.
 
+use odbc_api::sys::SqlDataType;
 use odbc_api::DataType;
 use sqlx_core::type_info::TypeInfo;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
+/// `SQL_SS_TIMESTAMPOFFSET`, SQL Server's `DATETIMEOFFSET`
+pub(crate) const SQL_SS_TIMESTAMPOFFSET: SqlDataType = SqlDataType(-155);
+
 /// Type information for an ODBC type.
 #[derive(Debug, Clone, Eq)]
 pub struct OdbcTypeInfo {
@@ -54,10 +58,63 @@ impl OdbcTypeInfo {
         Self { data_type: DataType::Double }
     }
 
+    pub const fn numeric(precision: usize, scale: i16) -> Self {
+        Self { data_type: DataType::Numeric { precision, scale } }
+    }
+
+    /// The scale of a `DECIMAL` or `NUMERIC` type
+    pub fn scale(&self) -> Option<i16> {
+        match self.data_type {
+            DataType::Decimal { scale, .. } | DataType::Numeric { scale, .. } => Some(scale),
+            _ => None,
+        }
+    }
+
     pub const fn date() -> Self {
         Self { data_type: DataType::Date }
     }
 
+    pub const fn time(precision: i16) -> Self {
+        Self { data_type: DataType::Time { precision } }
+    }
+
+    pub const fn timestamp(precision: i16) -> Self {
+        Self { data_type: DataType::Timestamp { precision } }
+    }
+
+    /// A timestamp with a UTC offset, reported as `SQL_SS_TIMESTAMPOFFSET` by SQL Server
+    pub const fn timestamp_offset(precision: i16) -> Self {
+        Self {
+            // `DATETIMEOFFSET(7)` is 34 characters as text
+            data_type: DataType::Other {
+                data_type: SQL_SS_TIMESTAMPOFFSET,
+                column_size: std::num::NonZero::new(34),
+                decimal_digits: precision,
+            },
+        }
+    }
+
+    /// Whether this is a timestamp with a UTC offset
+    pub fn is_timestamp_offset(&self) -> bool {
+        is_timestamp_offset(self.data_type)
+    }
+
+    /// A `SQL_GUID`, e.g. SQL Server's `UNIQUEIDENTIFIER`
+    pub const fn guid() -> Self {
+        Self {
+            data_type: DataType::Other {
+                data_type: SqlDataType::EXT_GUID,
+                column_size: std::num::NonZero::new(36),
+                decimal_digits: 0,
+            },
+        }
+    }
+
+    /// Whether this is a `SQL_GUID`
+    pub fn is_guid(&self) -> bool {
+        matches!(self.data_type, DataType::Other { data_type, .. } if data_type == SqlDataType::EXT_GUID)
+    }
+
     pub fn varchar(length: usize) -> Self {
         Self {
             data_type: DataType::Varchar { length: std::num::NonZero::new(length) },
@@ -120,10 +177,16 @@ pub fn data_type_name(dt: DataType) -> &'static str {
         DataType::WLongVarchar { .. } => "WLONGVARCHAR",
         DataType::WVarchar { .. } => "WVARCHAR",
         DataType::Unknown => "UNKNOWN",
+        dt if is_timestamp_offset(dt) => "DATETIMEOFFSET",
+        DataType::Other { data_type: SqlDataType::EXT_GUID, .. } => "GUID",
         DataType::Other { .. } => "OTHER",
     }
 }
 
+fn is_timestamp_offset(dt: DataType) -> bool {
+    matches!(dt, DataType::Other { data_type, .. } if data_type == SQL_SS_TIMESTAMPOFFSET)
+}
+
 /// Extension trait for DataType with helper methods
 pub trait DataTypeExt {
     /// Check if this is a character/string type
@@ -178,6 +241,6 @@ impl DataTypeExt for DataType {
         matches!(
             self,
             DataType::Date | DataType::Time { .. } | DataType::Timestamp { .. }
-        )
+        ) || is_timestamp_offset(self)
     }
 }
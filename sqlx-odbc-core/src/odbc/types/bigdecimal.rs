@@ -0,0 +1,60 @@
+//! `bigdecimal` support for arbitrary-precision `DECIMAL` and `NUMERIC` values.
+//!
+//! Values are sent as exact text in plain (non-exponent) notation.
+
+use crate::odbc::database::OdbcArgumentValue;
+use crate::odbc::type_info::DataTypeExt;
+use crate::odbc::{Odbc, OdbcTypeInfo, OdbcValueData, OdbcValueRef};
+use bigdecimal::BigDecimal;
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+use std::str::FromStr;
+
+impl Type<Odbc> for BigDecimal {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::numeric(38, 10)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        ty.data_type().accepts_numeric_data() || <String as Type<Odbc>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for BigDecimal {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Text(self.to_plain_string().into()));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for BigDecimal {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        let decimal = match value.data {
+            OdbcValueData::Text(s) => {
+                let s = s.trim();
+                BigDecimal::from_str(s).map_err(|e| format!("cannot decode '{s}' as BigDecimal: {e}"))?
+            }
+            OdbcValueData::TinyInt(i) => BigDecimal::from(*i),
+            OdbcValueData::SmallInt(i) => BigDecimal::from(*i),
+            OdbcValueData::Int(i) => BigDecimal::from(*i),
+            OdbcValueData::BigInt(i) => BigDecimal::from(*i),
+            OdbcValueData::Float(f) => BigDecimal::try_from(*f)?,
+            OdbcValueData::Double(f) => BigDecimal::try_from(*f)?,
+            _ => return Err("Cannot decode value as BigDecimal".into()),
+        };
+
+        // Report the scale of the column, e.g. 1.50 rather than 1.5 for NUMERIC(10, 2), but
+        // never drop digits the driver returned
+        match value.type_info.scale() {
+            Some(scale) if i64::from(scale) > decimal.fractional_digit_count() => {
+                Ok(decimal.with_scale(scale.into()))
+            }
+            _ => Ok(decimal),
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! `chrono` support for `DATE`, `TIME` and `TIMESTAMP` values.
+//!
+//! Values are decoded from the driver's date/time structs, or from ISO 8601 text for drivers
+//! that only return text.
+
+use crate::odbc::database::OdbcArgumentValue;
+use crate::odbc::{Odbc, OdbcTimestampOffset, OdbcTypeInfo, OdbcValueData, OdbcValueRef};
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+    Timelike, Utc,
+};
+use odbc_api::sys::{Date, Time, Timestamp};
+use odbc_api::DataType;
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S%.f";
+const DATETIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"];
+const OFFSET_DATETIME_FORMATS: [&str; 3] = [
+    "%Y-%m-%d %H:%M:%S%.f %#z",
+    "%Y-%m-%d %H:%M:%S%.f%#z",
+    "%Y-%m-%dT%H:%M:%S%.f%#z",
+];
+
+fn is_text(ty: &OdbcTypeInfo) -> bool {
+    <String as Type<Odbc>>::compatible(ty)
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+fn date_to_sql(date: NaiveDate) -> Result<Date, BoxDynError> {
+    // The range SQL defines for DATE, which also keeps the year within `i16`
+    if !(1..=9999).contains(&date.year()) {
+        return Err(format!("year of {date} is out of range for DATE").into());
+    }
+
+    Ok(Date {
+        year: date.year() as i16,
+        month: date.month() as u16,
+        day: date.day() as u16,
+    })
+}
+
+fn date_from_sql(date: &Date) -> Result<NaiveDate, BoxDynError> {
+    NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+        .ok_or_else(|| format!("invalid DATE {date:?}").into())
+}
+
+/// The nanoseconds of a time, rejecting chrono's leap-second representation.
+fn nanos(time: &NaiveTime) -> Result<u32, BoxDynError> {
+    match time.nanosecond() {
+        nanos @ 0..1_000_000_000 => Ok(nanos),
+        _ => Err(format!("leap second {time} cannot be encoded").into()),
+    }
+}
+
+fn time_from_sql(time: &Time) -> Result<NaiveTime, BoxDynError> {
+    NaiveTime::from_hms_opt(time.hour.into(), time.minute.into(), time.second.into())
+        .ok_or_else(|| format!("invalid TIME {time:?}").into())
+}
+
+fn timestamp_to_sql(datetime: NaiveDateTime) -> Result<Timestamp, BoxDynError> {
+    let date = date_to_sql(datetime.date())?;
+    let time = datetime.time();
+
+    Ok(Timestamp {
+        year: date.year,
+        month: date.month,
+        day: date.day,
+        hour: time.hour() as u16,
+        minute: time.minute() as u16,
+        second: time.second() as u16,
+        fraction: nanos(&time)?,
+    })
+}
+
+fn timestamp_from_sql(ts: &Timestamp) -> Result<NaiveDateTime, BoxDynError> {
+    let date = NaiveDate::from_ymd_opt(ts.year.into(), ts.month.into(), ts.day.into());
+    let time = NaiveTime::from_hms_nano_opt(
+        ts.hour.into(),
+        ts.minute.into(),
+        ts.second.into(),
+        ts.fraction,
+    );
+
+    match (date, time) {
+        (Some(date), Some(time)) => Ok(date.and_time(time)),
+        _ => Err(format!("invalid TIMESTAMP {ts:?}").into()),
+    }
+}
+
+fn timestamp_offset_to_sql(datetime: &DateTime<FixedOffset>) -> Result<OdbcTimestampOffset, BoxDynError> {
+    let ts = timestamp_to_sql(datetime.naive_local())?;
+
+    Ok(OdbcTimestampOffset {
+        year: ts.year,
+        month: ts.month,
+        day: ts.day,
+        hour: ts.hour,
+        minute: ts.minute,
+        second: ts.second,
+        fraction: ts.fraction,
+        ..Default::default()
+    }
+    .with_offset_seconds(datetime.offset().fix().local_minus_utc()))
+}
+
+fn timestamp_offset_from_sql(ts: &OdbcTimestampOffset) -> Result<DateTime<FixedOffset>, BoxDynError> {
+    let naive = timestamp_from_sql(&Timestamp {
+        year: ts.year,
+        month: ts.month,
+        day: ts.day,
+        hour: ts.hour,
+        minute: ts.minute,
+        second: ts.second,
+        fraction: ts.fraction,
+    })?;
+    let offset = FixedOffset::east_opt(ts.offset_seconds())
+        .ok_or_else(|| format!("invalid offset in {ts}"))?;
+
+    offset
+        .from_local_datetime(&naive)
+        .single()
+        .ok_or_else(|| format!("invalid timestamp {ts}").into())
+}
+
+fn parse_datetime_with_offset(s: &str) -> Option<DateTime<FixedOffset>> {
+    OFFSET_DATETIME_FORMATS
+        .iter()
+        .find_map(|format| DateTime::parse_from_str(s.trim(), format).ok())
+}
+
+fn parse_datetime(s: &str) -> Result<NaiveDateTime, BoxDynError> {
+    let s = s.trim();
+
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, DATE_FORMAT)
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+        .ok_or_else(|| format!("cannot parse '{s}' as a timestamp").into())
+}
+
+// ============================================================================
+// NaiveDate
+// ============================================================================
+
+impl Type<Odbc> for NaiveDate {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::date()
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        matches!(ty.data_type(), DataType::Date | DataType::Timestamp { .. }) || is_text(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for NaiveDate {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Date(date_to_sql(*self)?));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for NaiveDate {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            OdbcValueData::Date(date) => date_from_sql(date),
+            OdbcValueData::Timestamp(ts) => Ok(timestamp_from_sql(ts)?.date()),
+            OdbcValueData::Text(s) => NaiveDate::parse_from_str(s.trim(), DATE_FORMAT)
+                .or_else(|_| parse_datetime(s).map(|datetime| datetime.date())),
+            _ => Err("Cannot decode value as NaiveDate".into()),
+        }
+    }
+}
+
+// ============================================================================
+// NaiveTime
+// ============================================================================
+
+impl Type<Odbc> for NaiveTime {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::time(9)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        matches!(ty.data_type(), DataType::Time { .. } | DataType::Timestamp { .. }) || is_text(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for NaiveTime {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        // SQL_TIME_STRUCT has no fraction, so fractional times are sent as text
+        let value = match nanos(self)? {
+            0 => OdbcArgumentValue::Time(Time {
+                hour: self.hour() as u16,
+                minute: self.minute() as u16,
+                second: self.second() as u16,
+            }),
+            _ => OdbcArgumentValue::Text(self.format(TIME_FORMAT).to_string().into()),
+        };
+
+        buf.push(value);
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for NaiveTime {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            OdbcValueData::Time(time) => time_from_sql(time),
+            OdbcValueData::Timestamp(ts) => Ok(timestamp_from_sql(ts)?.time()),
+            OdbcValueData::Text(s) => NaiveTime::parse_from_str(s.trim(), TIME_FORMAT)
+                .or_else(|_| parse_datetime(s).map(|datetime| datetime.time())),
+            _ => Err("Cannot decode value as NaiveTime".into()),
+        }
+    }
+}
+
+// ============================================================================
+// NaiveDateTime
+// ============================================================================
+
+impl Type<Odbc> for NaiveDateTime {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::timestamp(9)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        matches!(ty.data_type(), DataType::Timestamp { .. } | DataType::Date) || is_text(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for NaiveDateTime {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Timestamp(timestamp_to_sql(*self)?));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for NaiveDateTime {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            OdbcValueData::Timestamp(ts) => timestamp_from_sql(ts),
+            OdbcValueData::Date(date) => Ok(date_from_sql(date)?.and_time(NaiveTime::MIN)),
+            OdbcValueData::Text(s) => parse_datetime(s),
+            _ => Err("Cannot decode value as NaiveDateTime".into()),
+        }
+    }
+}
+
+// ============================================================================
+// DateTime<Utc>
+// ============================================================================
+
+impl Type<Odbc> for DateTime<Utc> {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::timestamp(9)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        <DateTime<FixedOffset> as Type<Odbc>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for DateTime<Utc> {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Timestamp(timestamp_to_sql(self.naive_utc())?));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for DateTime<Utc> {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(<DateTime<FixedOffset> as Decode<Odbc>>::decode(value)?.with_timezone(&Utc))
+    }
+}
+
+// ============================================================================
+// DateTime<FixedOffset>
+// ============================================================================
+
+impl Type<Odbc> for DateTime<FixedOffset> {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::timestamp_offset(7)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        ty.is_timestamp_offset() || <NaiveDateTime as Type<Odbc>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for DateTime<FixedOffset> {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::TimestampOffset(timestamp_offset_to_sql(self)?));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for DateTime<FixedOffset> {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        // Values without an offset are taken as UTC
+        match value.data {
+            OdbcValueData::TimestampOffset(ts) => timestamp_offset_from_sql(ts),
+            OdbcValueData::Text(s) => match parse_datetime_with_offset(s) {
+                Some(datetime) => Ok(datetime),
+                None => Ok(parse_datetime(s)?.and_utc().fixed_offset()),
+            },
+            _ => Ok(<NaiveDateTime as Decode<Odbc>>::decode(value)?.and_utc().fixed_offset()),
+        }
+    }
+}
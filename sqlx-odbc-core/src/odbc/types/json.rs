@@ -0,0 +1,51 @@
+//! `serde_json` support for JSON stored in text columns (`NVARCHAR(MAX)` on SQL Server,
+//! `json`/`jsonb` on PostgreSQL).
+//!
+//! [`Json<T>`] is sent as serialized text; `serde_json::Value` and `&JsonRawValue` are
+//! covered through `sqlx_core`'s blanket implementations over `Json<T>`.
+
+use crate::odbc::database::OdbcArgumentValue;
+use crate::odbc::type_info::DataTypeExt;
+use crate::odbc::{Odbc, OdbcTypeInfo, OdbcValueData, OdbcValueRef};
+use odbc_api::DataType;
+use serde::{Deserialize, Serialize};
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::{Json, Type};
+
+impl<T> Type<Odbc> for Json<T> {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::new(DataType::WLongVarchar { length: None })
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        <String as Type<Odbc>>::compatible(ty) || ty.data_type().accepts_binary_data()
+    }
+}
+
+impl<'q, T> Encode<'q, Odbc> for Json<T>
+where
+    T: Serialize,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Text(self.encode_to_string()?.into()));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r, T> Decode<'r, Odbc> for Json<T>
+where
+    T: 'r + Deserialize<'r>,
+{
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            OdbcValueData::Text(s) => Json::decode_from_string(s),
+            OdbcValueData::Binary(bytes) => Json::decode_from_bytes(bytes),
+            _ => Err("Cannot decode value as JSON".into()),
+        }
+    }
+}
@@ -10,6 +10,22 @@ use sqlx_core::encode::{Encode, IsNull};
 use sqlx_core::error::BoxDynError;
 use sqlx_core::types::Type;
 
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal;
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "json")]
+pub use sqlx_core::types::Json;
+
 // ============================================================================
 // Boolean
 // ============================================================================
@@ -172,6 +188,154 @@ impl<'r> Decode<'r, Odbc> for i64 {
     }
 }
 
+// ============================================================================
+// Unsigned Integers
+// ============================================================================
+//
+// Unsigned values are bound as the next-wider signed type so they never wrap, and `u64`
+// values above `i64::MAX` are rejected. Decoding is range checked; `TINYINT` is read as
+// unsigned because SQL Server's `TINYINT` is 0..=255.
+
+fn is_integer(ty: &OdbcTypeInfo) -> bool {
+    use odbc_api::DataType;
+    matches!(
+        ty.data_type(),
+        DataType::TinyInt | DataType::SmallInt | DataType::Integer | DataType::BigInt
+    )
+}
+
+fn decode_unsigned<T>(value: OdbcValueRef<'_>, name: &str) -> Result<T, BoxDynError>
+where
+    T: TryFrom<i64> + std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let n = match value.data {
+        OdbcValueData::TinyInt(i) => i64::from(*i as u8),
+        OdbcValueData::SmallInt(i) => i64::from(*i),
+        OdbcValueData::Int(i) => i64::from(*i),
+        OdbcValueData::BigInt(i) => *i,
+        OdbcValueData::Text(s) => {
+            return s
+                .trim()
+                .parse()
+                .map_err(|e| format!("Cannot decode '{}' as {}: {}", s, name, e).into());
+        }
+        _ => return Err(format!("Cannot decode value as {}", name).into()),
+    };
+
+    if n < 0 {
+        return Err(format!("Negative value {} is out of range for {}", n, name).into());
+    }
+
+    T::try_from(n).map_err(|_| format!("Value {} is out of range for {}", n, name).into())
+}
+
+impl Type<Odbc> for u8 {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::small_int()
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        is_integer(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for u8 {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::SmallInt(i16::from(*self)));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for u8 {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        decode_unsigned(value, "u8")
+    }
+}
+
+impl Type<Odbc> for u16 {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::integer()
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        is_integer(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for u16 {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Int(i32::from(*self)));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for u16 {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        decode_unsigned(value, "u16")
+    }
+}
+
+impl Type<Odbc> for u32 {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::big_int()
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        is_integer(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for u32 {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::BigInt(i64::from(*self)));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for u32 {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        decode_unsigned(value, "u32")
+    }
+}
+
+impl Type<Odbc> for u64 {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::big_int()
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        is_integer(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for u64 {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        let value = i64::try_from(*self)
+            .map_err(|_| format!("Value {} is out of range for BIGINT", self))?;
+        buf.push(OdbcArgumentValue::BigInt(value));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for u64 {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        decode_unsigned(value, "u64")
+    }
+}
+
 // ============================================================================
 // Floating Point
 // ============================================================================
@@ -276,6 +440,7 @@ impl<'r> Decode<'r, Odbc> for String {
             OdbcValueData::Float(f) => Ok(f.to_string()),
             OdbcValueData::Double(f) => Ok(f.to_string()),
             OdbcValueData::Bool(b) => Ok(b.to_string()),
+            OdbcValueData::TimestampOffset(ts) => Ok(ts.to_string()),
             _ => Err("Cannot decode value as String".into()),
         }
     }
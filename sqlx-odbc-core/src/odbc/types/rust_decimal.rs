@@ -0,0 +1,71 @@
+//! `rust_decimal` support for `DECIMAL` and `NUMERIC` values.
+//!
+//! Values are sent as exact text, which every driver converts without going through a
+//! floating point type.
+
+use crate::odbc::database::OdbcArgumentValue;
+use crate::odbc::type_info::DataTypeExt;
+use crate::odbc::{Odbc, OdbcTypeInfo, OdbcValueData, OdbcValueRef};
+use rust_decimal::Decimal;
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+
+impl Type<Odbc> for Decimal {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::numeric(28, 10)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        ty.data_type().accepts_numeric_data() || <String as Type<Odbc>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for Decimal {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Text(self.to_string().into()));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for Decimal {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        let scale = value.type_info.scale();
+        if let Some(scale) = scale
+            && !(0..=Decimal::MAX_SCALE as i16).contains(&scale)
+        {
+            return Err(format!(
+                "{} scale {scale} is out of range for Decimal (0 to {})",
+                value.type_info,
+                Decimal::MAX_SCALE
+            )
+            .into());
+        }
+
+        let mut decimal = match value.data {
+            OdbcValueData::Text(s) => {
+                let s = s.trim();
+                Decimal::from_str_exact(s)
+                    .or_else(|_| Decimal::from_scientific(s))
+                    .map_err(|e| format!("cannot decode '{s}' as Decimal: {e}"))?
+            }
+            OdbcValueData::TinyInt(i) => Decimal::from(*i),
+            OdbcValueData::SmallInt(i) => Decimal::from(*i),
+            OdbcValueData::Int(i) => Decimal::from(*i),
+            OdbcValueData::BigInt(i) => Decimal::from(*i),
+            OdbcValueData::Float(f) => Decimal::try_from(*f)?,
+            OdbcValueData::Double(f) => Decimal::try_from(*f)?,
+            _ => return Err("Cannot decode value as Decimal".into()),
+        };
+
+        // Report the scale of the column, e.g. 1.50 rather than 1.5 for DECIMAL(10, 2)
+        if let Some(scale) = scale {
+            decimal.rescale(scale as u32);
+        }
+        Ok(decimal)
+    }
+}
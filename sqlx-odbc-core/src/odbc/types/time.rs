@@ -0,0 +1,272 @@
+//! `time` support for `DATE`, `TIME` and `TIMESTAMP` values.
+//!
+//! Values are decoded from the driver's date/time structs, or from ISO 8601 text for drivers
+//! that only return text.
+
+use crate::odbc::database::OdbcArgumentValue;
+use crate::odbc::{Odbc, OdbcTimestampOffset, OdbcTypeInfo, OdbcValueData, OdbcValueRef};
+use odbc_api::sys::{Date as SqlDate, Time as SqlTime, Timestamp};
+use odbc_api::DataType;
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+use time::format_description::BorrowedFormatItem;
+use time::macros::format_description;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+const DATE_FORMAT: &[BorrowedFormatItem<'_>] = format_description!("[year]-[month]-[day]");
+const TIME_FORMAT: &[BorrowedFormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+const DATETIME_FORMAT: &[BorrowedFormatItem<'_>] = format_description!(
+    "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]]"
+);
+const OFFSET_DATETIME_FORMAT: &[BorrowedFormatItem<'_>] = format_description!(
+    "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]][optional [ ]][offset_hour][optional [:[offset_minute]]]"
+);
+
+fn is_text(ty: &OdbcTypeInfo) -> bool {
+    <String as Type<Odbc>>::compatible(ty)
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+fn date_to_sql(date: Date) -> Result<SqlDate, BoxDynError> {
+    // The range SQL defines for DATE, which also keeps the year within `i16`
+    if !(1..=9999).contains(&date.year()) {
+        return Err(format!("year of {date} is out of range for DATE").into());
+    }
+
+    Ok(SqlDate {
+        year: date.year() as i16,
+        month: u8::from(date.month()).into(),
+        day: date.day().into(),
+    })
+}
+
+fn date_from_sql(year: i16, month: u16, day: u16) -> Result<Date, BoxDynError> {
+    let month = Month::try_from(u8::try_from(month)?)?;
+    Ok(Date::from_calendar_date(year.into(), month, u8::try_from(day)?)?)
+}
+
+fn time_from_sql(hour: u16, minute: u16, second: u16, nanos: u32) -> Result<Time, BoxDynError> {
+    Ok(Time::from_hms_nano(
+        u8::try_from(hour)?,
+        u8::try_from(minute)?,
+        u8::try_from(second)?,
+        nanos,
+    )?)
+}
+
+fn timestamp_to_sql(datetime: PrimitiveDateTime) -> Result<Timestamp, BoxDynError> {
+    let date = date_to_sql(datetime.date())?;
+
+    Ok(Timestamp {
+        year: date.year,
+        month: date.month,
+        day: date.day,
+        hour: datetime.hour().into(),
+        minute: datetime.minute().into(),
+        second: datetime.second().into(),
+        fraction: datetime.nanosecond(),
+    })
+}
+
+fn timestamp_from_sql(ts: &Timestamp) -> Result<PrimitiveDateTime, BoxDynError> {
+    let date = date_from_sql(ts.year, ts.month, ts.day)?;
+    let time = time_from_sql(ts.hour, ts.minute, ts.second, ts.fraction)?;
+    Ok(PrimitiveDateTime::new(date, time))
+}
+
+fn timestamp_offset_to_sql(datetime: OffsetDateTime) -> Result<OdbcTimestampOffset, BoxDynError> {
+    let ts = timestamp_to_sql(PrimitiveDateTime::new(datetime.date(), datetime.time()))?;
+
+    Ok(OdbcTimestampOffset {
+        year: ts.year,
+        month: ts.month,
+        day: ts.day,
+        hour: ts.hour,
+        minute: ts.minute,
+        second: ts.second,
+        fraction: ts.fraction,
+        ..Default::default()
+    }
+    .with_offset_seconds(datetime.offset().whole_seconds()))
+}
+
+fn timestamp_offset_from_sql(ts: &OdbcTimestampOffset) -> Result<OffsetDateTime, BoxDynError> {
+    let date = date_from_sql(ts.year, ts.month, ts.day)?;
+    let time = time_from_sql(ts.hour, ts.minute, ts.second, ts.fraction)?;
+    let offset = UtcOffset::from_whole_seconds(ts.offset_seconds())?;
+    Ok(PrimitiveDateTime::new(date, time).assume_offset(offset))
+}
+
+/// Parse ISO 8601 text, accepting `T` as well as a space between date and time.
+fn parse_datetime(s: &str) -> Result<PrimitiveDateTime, BoxDynError> {
+    let s = s.trim().replacen('T', " ", 1);
+
+    PrimitiveDateTime::parse(&s, DATETIME_FORMAT)
+        .or_else(|_| Date::parse(&s, DATE_FORMAT).map(|date| date.midnight()))
+        .map_err(|_| format!("cannot parse '{s}' as a timestamp").into())
+}
+
+// ============================================================================
+// Date
+// ============================================================================
+
+impl Type<Odbc> for Date {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::date()
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        matches!(ty.data_type(), DataType::Date | DataType::Timestamp { .. }) || is_text(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for Date {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Date(date_to_sql(*self)?));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for Date {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            OdbcValueData::Date(date) => date_from_sql(date.year, date.month, date.day),
+            OdbcValueData::Timestamp(ts) => Ok(timestamp_from_sql(ts)?.date()),
+            OdbcValueData::Text(s) => Date::parse(s.trim(), DATE_FORMAT)
+                .or_else(|_| parse_datetime(s).map(|datetime| datetime.date())),
+            _ => Err("Cannot decode value as Date".into()),
+        }
+    }
+}
+
+// ============================================================================
+// Time
+// ============================================================================
+
+impl Type<Odbc> for Time {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::time(9)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        matches!(ty.data_type(), DataType::Time { .. } | DataType::Timestamp { .. }) || is_text(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for Time {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        // SQL_TIME_STRUCT has no fraction, so fractional times are sent as text
+        let value = match self.nanosecond() {
+            0 => OdbcArgumentValue::Time(SqlTime {
+                hour: self.hour().into(),
+                minute: self.minute().into(),
+                second: self.second().into(),
+            }),
+            _ => OdbcArgumentValue::Text(self.format(TIME_FORMAT)?.into()),
+        };
+
+        buf.push(value);
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for Time {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            OdbcValueData::Time(time) => time_from_sql(time.hour, time.minute, time.second, 0),
+            OdbcValueData::Timestamp(ts) => Ok(timestamp_from_sql(ts)?.time()),
+            OdbcValueData::Text(s) => Time::parse(s.trim(), TIME_FORMAT)
+                .or_else(|_| parse_datetime(s).map(|datetime| datetime.time())),
+            _ => Err("Cannot decode value as Time".into()),
+        }
+    }
+}
+
+// ============================================================================
+// PrimitiveDateTime
+// ============================================================================
+
+impl Type<Odbc> for PrimitiveDateTime {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::timestamp(9)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        matches!(ty.data_type(), DataType::Timestamp { .. } | DataType::Date) || is_text(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for PrimitiveDateTime {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::Timestamp(timestamp_to_sql(*self)?));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for PrimitiveDateTime {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            OdbcValueData::Timestamp(ts) => timestamp_from_sql(ts),
+            OdbcValueData::Date(date) => {
+                Ok(date_from_sql(date.year, date.month, date.day)?.midnight())
+            }
+            OdbcValueData::Text(s) => parse_datetime(s),
+            _ => Err("Cannot decode value as PrimitiveDateTime".into()),
+        }
+    }
+}
+
+// ============================================================================
+// OffsetDateTime
+// ============================================================================
+
+impl Type<Odbc> for OffsetDateTime {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::timestamp_offset(7)
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        ty.is_timestamp_offset() || <PrimitiveDateTime as Type<Odbc>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for OffsetDateTime {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(OdbcArgumentValue::TimestampOffset(timestamp_offset_to_sql(*self)?));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for OffsetDateTime {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        // Values without an offset are taken as UTC
+        match value.data {
+            OdbcValueData::TimestampOffset(ts) => timestamp_offset_from_sql(ts),
+            OdbcValueData::Text(s) => {
+                match OffsetDateTime::parse(&s.trim().replacen('T', " ", 1), OFFSET_DATETIME_FORMAT) {
+                    Ok(datetime) => Ok(datetime),
+                    Err(_) => Ok(parse_datetime(s)?.assume_utc()),
+                }
+            }
+            _ => Ok(<PrimitiveDateTime as Decode<Odbc>>::decode(value)?.assume_utc()),
+        }
+    }
+}
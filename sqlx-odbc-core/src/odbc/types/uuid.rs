@@ -0,0 +1,61 @@
+//! `uuid` support for `SQL_GUID` values (`UNIQUEIDENTIFIER` on SQL Server, `uuid` on
+//! PostgreSQL).
+//!
+//! UUIDs are bound as `SQL_GUID` structs and decoded from those, from text, or from 16 raw
+//! bytes. Raw bytes of a `SQL_GUID` column are in the driver's `GUID` struct layout, which
+//! stores the first three fields little-endian, as SQL Server does; bytes of any other column
+//! (e.g. `BINARY(16)`) are taken in RFC 4122 order.
+
+use crate::odbc::database::OdbcArgumentValue;
+use crate::odbc::type_info::DataTypeExt;
+use crate::odbc::{Odbc, OdbcTypeInfo, OdbcValueData, OdbcValueRef};
+use odbc_api::sys::Guid;
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+use uuid::Uuid;
+
+impl Type<Odbc> for Uuid {
+    fn type_info() -> OdbcTypeInfo {
+        OdbcTypeInfo::guid()
+    }
+
+    fn compatible(ty: &OdbcTypeInfo) -> bool {
+        ty.is_guid()
+            || ty.data_type().accepts_binary_data()
+            || <String as Type<Odbc>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Odbc> for Uuid {
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<OdbcArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        let (d1, d2, d3, d4) = self.as_fields();
+        buf.push(OdbcArgumentValue::Guid(Guid { d1, d2, d3, d4: *d4 }));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Odbc> for Uuid {
+    fn decode(value: OdbcValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.data {
+            OdbcValueData::Guid(guid) => Ok(Uuid::from_fields(guid.d1, guid.d2, guid.d3, &guid.d4)),
+            OdbcValueData::Text(s) => Ok(Uuid::try_parse(s.trim())?),
+            OdbcValueData::Binary(bytes) => {
+                let bytes: [u8; 16] = bytes.as_slice().try_into().map_err(|_| {
+                    format!("expected 16 bytes for Uuid, got {}", bytes.len())
+                })?;
+
+                if value.type_info.is_guid() {
+                    Ok(Uuid::from_bytes_le(bytes))
+                } else {
+                    Ok(Uuid::from_bytes(bytes))
+                }
+            }
+            _ => Err("Cannot decode value as Uuid".into()),
+        }
+    }
+}
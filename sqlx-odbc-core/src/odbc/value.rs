@@ -1,8 +1,11 @@
 //! ODBC value types.
 
 use crate::odbc::{Odbc, OdbcTypeInfo};
+use sqlx_core::error::BoxDynError;
 use sqlx_core::value::{Value, ValueRef};
 use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
 
 /// Enum containing an owned value for all supported ODBC types.
 #[derive(Debug, Clone)]
@@ -33,6 +36,131 @@ pub enum OdbcValueData {
     Time(odbc_api::sys::Time),
     /// Timestamp value
     Timestamp(odbc_api::sys::Timestamp),
+    /// Timestamp with a UTC offset
+    TimestampOffset(OdbcTimestampOffset),
+    /// GUID value
+    Guid(odbc_api::sys::Guid),
+}
+
+/// A timestamp with a UTC offset, laid out like SQL Server's `SQL_SS_TIMESTAMPOFFSET_STRUCT`.
+///
+/// Returned for `DATETIMEOFFSET` columns. Offset-aware types from the `chrono` and `time`
+/// features encode to and decode from it.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OdbcTimestampOffset {
+    pub year: i16,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+    /// Nanoseconds
+    pub fraction: u32,
+    /// Hours of the offset from UTC
+    pub timezone_hour: i16,
+    /// Minutes of the offset from UTC, with the same sign as `timezone_hour`
+    pub timezone_minute: i16,
+}
+
+impl OdbcTimestampOffset {
+    /// The offset from UTC in seconds
+    pub fn offset_seconds(&self) -> i32 {
+        i32::from(self.timezone_hour) * 3600 + i32::from(self.timezone_minute) * 60
+    }
+
+    /// Set the offset from UTC, truncated to whole minutes
+    pub fn with_offset_seconds(mut self, seconds: i32) -> Self {
+        let minutes = seconds / 60;
+        self.timezone_hour = (minutes / 60) as i16;
+        self.timezone_minute = (minutes % 60) as i16;
+        self
+    }
+}
+
+/// Formats as ISO 8601 text, e.g. `2024-01-02 03:04:05.000000000 +01:00`.
+impl Display for OdbcTimestampOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let sign = if self.offset_seconds() < 0 { '-' } else { '+' };
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:09} {sign}{:02}:{:02}",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            self.fraction,
+            self.timezone_hour.unsigned_abs(),
+            self.timezone_minute.unsigned_abs(),
+        )
+    }
+}
+
+/// Parses ISO 8601 text as returned by drivers, e.g. `2024-01-02 03:04:05.1234567 +01:00`
+/// from SQL Server or `2024-01-02 03:04:05+01` from PostgreSQL.
+///
+/// Only the syntax is checked; the fields are not validated as a calendar date.
+impl FromStr for OdbcTimestampOffset {
+    type Err = BoxDynError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_timestamp_offset(s.trim())
+            .ok_or_else(|| format!("cannot parse '{s}' as a timestamp with offset").into())
+    }
+}
+
+fn parse_timestamp_offset(s: &str) -> Option<OdbcTimestampOffset> {
+    let (datetime, offset) = match s.strip_suffix('Z') {
+        Some(datetime) => (datetime, "+00"),
+        None => {
+            // The offset sign comes after the date, whose own dashes are in the first 10 bytes
+            let pos = 10 + s.get(10..)?.rfind(['+', '-'])?;
+            s.split_at(pos)
+        }
+    };
+
+    let (date, time) = datetime.trim_end().split_once([' ', 'T'])?;
+
+    let mut date = date.splitn(3, '-');
+    let year = date.next()?.parse().ok()?;
+    let month = date.next()?.parse().ok()?;
+    let day = date.next()?.parse().ok()?;
+
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time = time.splitn(3, ':');
+    let hour = time.next()?.parse().ok()?;
+    let minute = time.next()?.parse().ok()?;
+    let second = time.next()?.parse().ok()?;
+
+    let fraction = match fraction.len() {
+        0 => 0,
+        len @ 1..=9 if fraction.bytes().all(|b| b.is_ascii_digit()) => {
+            fraction.parse::<u32>().ok()? * 10u32.pow(9 - len as u32)
+        }
+        _ => return None,
+    };
+
+    let (sign, offset) = offset.split_at(1);
+    let sign = if sign == "-" { -1 } else { 1 };
+    let (offset_hour, offset_minute) = match offset.split_once(':') {
+        Some(parts) => parts,
+        None if offset.len() == 4 => offset.split_at(2),
+        None => (offset, "0"),
+    };
+
+    Some(OdbcTimestampOffset {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        fraction,
+        timezone_hour: sign * offset_hour.parse::<i16>().ok()?,
+        timezone_minute: sign * offset_minute.parse::<i16>().ok()?,
+    })
 }
 
 /// A reference to a value from an ODBC result.
@@ -4,14 +4,13 @@ use heck::ToSnakeCase;
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::{
-    spanned::Spanned, Data, DeriveInput, Fields, LitStr,
+    parse_quote, spanned::Spanned, Data, DeriveInput, Fields, LitStr, WherePredicate,
 };
 
 pub fn expand_derive_from_row(input: TokenStream) -> syn::Result<TokenStream> {
     let input: DeriveInput = syn::parse2(input)?;
     
     let name = &input.ident;
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     
     let fields = match &input.data {
         Data::Struct(data) => match &data.fields {
@@ -44,6 +43,7 @@ pub fn expand_derive_from_row(input: TokenStream) -> syn::Result<TokenStream> {
     };
 
     let mut field_initializers = Vec::new();
+    let mut predicates: Vec<WherePredicate> = Vec::new();
     
     for field in fields {
         let field_ident = field.ident.as_ref().unwrap();
@@ -53,6 +53,7 @@ pub fn expand_derive_from_row(input: TokenStream) -> syn::Result<TokenStream> {
         let mut skip = false;
         let mut use_default = false;
         let mut flatten = false;
+        let mut json = false;
         
         // Parse attributes
         for attr in &field.attrs {
@@ -71,11 +72,35 @@ pub fn expand_derive_from_row(input: TokenStream) -> syn::Result<TokenStream> {
                     use_default = true;
                 } else if meta.path.is_ident("flatten") {
                     flatten = true;
+                } else if meta.path.is_ident("json") {
+                    json = true;
                 }
                 Ok(())
             })?;
         }
         
+        // The type read from the row; `json` fields are decoded through `Json<T>`
+        let decode_ty = if json {
+            quote! { ::sqlx_core::types::Json<#field_ty> }
+        } else {
+            quote! { #field_ty }
+        };
+        let unwrap_json = json.then(|| quote! { .0 });
+
+        if skip {
+            predicates.push(parse_quote!(#field_ty: ::std::default::Default));
+        } else if flatten {
+            predicates.push(parse_quote!(#field_ty: ::sqlx_core::from_row::FromRow<'r, R>));
+        } else {
+            predicates.push(parse_quote!(
+                #decode_ty: ::sqlx_core::decode::Decode<'r, R::Database>
+                    + ::sqlx_core::types::Type<R::Database>
+            ));
+            if use_default {
+                predicates.push(parse_quote!(#field_ty: ::std::default::Default));
+            }
+        }
+
         let initializer = if skip {
             quote_spanned! { field.span() =>
                 #field_ident: ::std::default::Default::default()
@@ -86,23 +111,33 @@ pub fn expand_derive_from_row(input: TokenStream) -> syn::Result<TokenStream> {
             }
         } else if use_default {
             quote_spanned! { field.span() =>
-                #field_ident: row.try_get::<#field_ty, _>(#column_name)
+                #field_ident: row.try_get::<#decode_ty, _>(#column_name)
+                    .map(|value| value #unwrap_json)
                     .unwrap_or_default()
             }
         } else {
             quote_spanned! { field.span() =>
-                #field_ident: row.try_get(#column_name)?
+                #field_ident: row.try_get::<#decode_ty, _>(#column_name)? #unwrap_json
             }
         };
         
         field_initializers.push(initializer);
     }
     
+    // `'r` and `R` are added to the struct's own generics for the impl
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('r));
+    generics.params.push(parse_quote!(R: ::sqlx_core::row::Row));
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.push(parse_quote!(&'r str: ::sqlx_core::column::ColumnIndex<R>));
+    where_clause.predicates.extend(predicates);
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
     let expanded = quote! {
         impl #impl_generics ::sqlx_core::from_row::FromRow<'r, R> for #name #ty_generics
         #where_clause
-        where
-            R: ::sqlx_core::row::Row,
         {
             fn from_row(row: &'r R) -> ::std::result::Result<Self, ::sqlx_core::Error> {
                 use ::sqlx_core::row::Row;
@@ -37,6 +37,7 @@ mod query;
 /// - `#[sqlx(skip)]` - Skip this field when reading from the row (requires Default)
 /// - `#[sqlx(default)]` - Use Default::default() if the column is NULL or missing
 /// - `#[sqlx(flatten)]` - Flatten nested structs that also implement FromRow
+/// - `#[sqlx(json)]` - Decode the column as JSON into the field's type (requires the `json` feature)
 #[cfg(feature = "derive")]
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
 #[proc_macro_derive(FromRow, attributes(sqlx))]
@@ -33,6 +33,12 @@
 //! - `query` - Enable compile-time verified query macros (`query!`, `query_as!`, etc.)
 //! - `mssql-migrate` - Enable Microsoft SQL Server migration support
 //! - `postgres-migrate` - Enable PostgreSQL migration support
+//! - `chrono` - Enable `chrono` date and time types
+//! - `time` - Enable `time` date and time types
+//! - `rust_decimal` - Enable `rust_decimal::Decimal` for `DECIMAL`/`NUMERIC`
+//! - `bigdecimal` - Enable `bigdecimal::BigDecimal` for arbitrary-precision `NUMERIC`
+//! - `uuid` - Enable `uuid::Uuid` for `GUID`/`UNIQUEIDENTIFIER`
+//! - `json` - Enable `Json<T>` and `serde_json::Value` over text columns
 //! - `serde` - Enable serde serialization support
 //! - `offline` - Enable offline mode support
 
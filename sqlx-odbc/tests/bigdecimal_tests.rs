@@ -0,0 +1,37 @@
+//! Tests for the `bigdecimal` type integration.
+
+#![cfg(feature = "bigdecimal")]
+
+mod common;
+
+use bigdecimal::BigDecimal;
+use common::encode;
+use sqlx_odbc::odbc::{OdbcArgumentValue, OdbcTypeInfo, OdbcValueData};
+use std::str::FromStr;
+
+fn decode(text: &str, type_info: OdbcTypeInfo) -> BigDecimal {
+    common::decode(OdbcValueData::Text(text.into()), type_info)
+}
+
+#[test]
+fn test_decode_beyond_28_digits() {
+    let text = "1234567890123456789012345678.1234567890";
+    let value = decode(text, OdbcTypeInfo::numeric(38, 10));
+    assert_eq!(value.to_plain_string(), text);
+}
+
+#[test]
+fn test_decode_keeps_column_scale() {
+    assert_eq!(decode("1.5", OdbcTypeInfo::numeric(10, 2)).to_plain_string(), "1.50");
+    // Digits beyond the reported scale are kept
+    assert_eq!(decode("1.125", OdbcTypeInfo::numeric(10, 2)).to_plain_string(), "1.125");
+}
+
+#[test]
+fn test_encode_as_plain_text() {
+    let value = BigDecimal::from_str("1e30").unwrap();
+    assert!(matches!(
+        encode(value),
+        OdbcArgumentValue::Text(s) if s == "1000000000000000000000000000000"
+    ));
+}
@@ -0,0 +1,90 @@
+//! Tests for the `chrono` type integration.
+
+#![cfg(feature = "chrono")]
+
+mod common;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use common::{decode, encode, try_decode, try_encode};
+use sqlx_odbc::odbc::{OdbcArgumentValue, OdbcTypeInfo, OdbcValueData};
+
+#[test]
+fn test_timestamp_round_trip_keeps_nanoseconds() {
+    let datetime = NaiveDate::from_ymd_opt(2024, 2, 29)
+        .unwrap()
+        .and_hms_nano_opt(13, 14, 15, 123_456_789)
+        .unwrap();
+
+    let OdbcArgumentValue::Timestamp(ts) = encode(datetime) else {
+        panic!("NaiveDateTime should encode as a timestamp");
+    };
+    assert_eq!(ts.fraction, 123_456_789);
+    assert_eq!(
+        decode::<NaiveDateTime>(OdbcValueData::Timestamp(ts), OdbcTypeInfo::timestamp(7)),
+        datetime
+    );
+}
+
+#[test]
+fn test_decode_iso_text() {
+    assert_eq!(
+        decode::<NaiveDate>(OdbcValueData::Text("2024-02-29".into()), OdbcTypeInfo::date()),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+    );
+    assert_eq!(
+        decode::<NaiveTime>(OdbcValueData::Text("13:14:15.1234567".into()), OdbcTypeInfo::time(7)),
+        NaiveTime::from_hms_nano_opt(13, 14, 15, 123_456_700).unwrap()
+    );
+    assert_eq!(
+        decode::<NaiveDateTime>(
+            OdbcValueData::Text("2024-02-29T13:14:15".into()),
+            OdbcTypeInfo::timestamp(7)
+        ),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_opt(13, 14, 15).unwrap()
+    );
+    assert_eq!(
+        decode::<DateTime<Utc>>(
+            OdbcValueData::Text("2024-02-29 13:14:15 +02:00".into()),
+            OdbcTypeInfo::timestamp_offset(7)
+        ),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_opt(11, 14, 15).unwrap().and_utc()
+    );
+}
+
+#[test]
+fn test_fixed_offset_round_trip_keeps_offset() {
+    let value = DateTime::parse_from_rfc3339("2024-02-29T13:14:15.5-03:30").unwrap();
+
+    let OdbcArgumentValue::TimestampOffset(ts) = encode(value) else {
+        panic!("DateTime<FixedOffset> should encode as a timestamp with offset");
+    };
+    assert_eq!((ts.hour, ts.timezone_hour, ts.timezone_minute), (13, -3, -30));
+    let type_info = OdbcTypeInfo::timestamp_offset(7);
+    assert_eq!(
+        decode::<DateTime<FixedOffset>>(OdbcValueData::TimestampOffset(ts), type_info.clone()),
+        value
+    );
+    assert_eq!(
+        decode::<DateTime<Utc>>(OdbcValueData::TimestampOffset(ts), type_info.clone()),
+        value.to_utc()
+    );
+    assert_eq!(
+        decode::<DateTime<FixedOffset>>(
+            OdbcValueData::Text("2024-02-29 13:14:15.5-03:30".into()),
+            type_info
+        ),
+        value
+    );
+}
+
+#[test]
+fn test_out_of_range_values_are_rejected() {
+    for year in [0, 10_000, 40_000] {
+        let date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+        let err = try_encode(date).expect_err("year should be out of range");
+        assert!(err.contains("out of range for DATE"), "Unexpected error: {err}");
+    }
+
+    let invalid = OdbcValueData::Text("2023-02-29".into());
+    assert!(try_decode::<NaiveDate>(invalid, OdbcTypeInfo::date()).is_err());
+}
@@ -0,0 +1,53 @@
+//! Helpers shared by the integration tests.
+//!
+//! Each test binary only uses some of them.
+#![allow(dead_code)]
+
+use sqlx_odbc::odbc::{
+    Odbc, OdbcArgumentValue, OdbcConnectOptions, OdbcConnection, OdbcTypeInfo, OdbcValueData,
+    OdbcValueRef,
+};
+use sqlx_odbc::sqlx_core::decode::Decode;
+use sqlx_odbc::sqlx_core::encode::{Encode, IsNull};
+
+/// Decode a value read from a column of type `type_info`.
+pub fn try_decode<T: for<'r> Decode<'r, Odbc>>(
+    data: OdbcValueData,
+    type_info: OdbcTypeInfo,
+) -> Result<T, String> {
+    T::decode(OdbcValueRef::new(&data, type_info)).map_err(|e| e.to_string())
+}
+
+/// Decode a value read from a column of type `type_info`, panicking on failure.
+pub fn decode<T: for<'r> Decode<'r, Odbc>>(data: OdbcValueData, type_info: OdbcTypeInfo) -> T {
+    try_decode(data, type_info).unwrap_or_else(|e| panic!("value should decode: {e}"))
+}
+
+/// Encode a value into the argument it is bound as.
+pub fn try_encode<'q, T: Encode<'q, Odbc>>(value: T) -> Result<OdbcArgumentValue<'q>, String> {
+    let mut buf = Vec::new();
+    match value.encode_by_ref(&mut buf).map_err(|e| e.to_string())? {
+        IsNull::No => Ok(buf.pop().expect("encoding should push an argument")),
+        IsNull::Yes => Err("value encoded as NULL".into()),
+    }
+}
+
+/// Encode a value into the argument it is bound as, panicking on failure.
+pub fn encode<'q, T: Encode<'q, Odbc>>(value: T) -> OdbcArgumentValue<'q> {
+    try_encode(value).unwrap_or_else(|e| panic!("value should encode: {e}"))
+}
+
+/// Get the connection string from environment or use default for local dev
+pub fn get_connection_string() -> String {
+    std::env::var("DATABASE_URL").unwrap_or_else(|_| {
+        "Driver={ODBC Driver 18 for SQL Server};Server=mssql,1433;Database=master;Uid=sa;Pwd=YourStrong@Passw0rd;TrustServerCertificate=yes".to_string()
+    })
+}
+
+/// Helper to establish a connection for tests
+pub async fn connect() -> OdbcConnection {
+    let options = OdbcConnectOptions::new(get_connection_string());
+    OdbcConnection::establish(&options)
+        .await
+        .expect("Failed to connect to database")
+}
@@ -8,24 +8,16 @@
 //! - Transactions work correctly across operations
 //! - Session state is maintained
 
+mod common;
+
+use common::{connect, get_connection_string};
 use sqlx_odbc::odbc::{OdbcConnectOptions, OdbcConnection};
 use sqlx_odbc::sqlx_core::connection::Connection;
 use sqlx_odbc::sqlx_core::executor::Executor;
 
-/// Get the connection string from environment or use default for local dev
-fn get_connection_string() -> String {
-    std::env::var("DATABASE_URL").unwrap_or_else(|_| {
-        "Driver={ODBC Driver 18 for SQL Server};Server=mssql,1433;Database=master;Uid=sa;Pwd=YourStrong@Passw0rd;TrustServerCertificate=yes".to_string()
-    })
-}
-
-/// Helper to establish a connection for tests
-async fn connect() -> OdbcConnection {
-    let options = OdbcConnectOptions::new(get_connection_string());
-    OdbcConnection::establish(&options)
-        .await
-        .expect("Failed to connect to database")
-}
+/// The isolation level of the current session (2 is READ COMMITTED, 4 is SERIALIZABLE)
+const ISOLATION_QUERY: &str =
+    "SELECT transaction_isolation_level AS level FROM sys.dm_exec_sessions WHERE session_id = @@SPID";
 
 #[tokio::test]
 async fn test_connection_establish() {
@@ -56,6 +48,18 @@ async fn test_connection_ping() {
     assert!(result.is_ok(), "Ping should succeed: {:?}", result.err());
 }
 
+#[tokio::test]
+async fn test_ping_always_runs_validation_query() {
+    // A live connection reports itself as not dead, so only the query can fail the ping
+    let options = OdbcConnectOptions::new(get_connection_string())
+        .validation_query("SELECT * FROM ping_validation_table_that_does_not_exist");
+    let mut conn = OdbcConnection::establish(&options)
+        .await
+        .expect("Failed to connect to database");
+
+    assert!(conn.ping().await.is_err(), "Ping should run the validation query");
+}
+
 #[tokio::test]
 async fn test_connection_close() {
     let conn = connect().await;
@@ -344,3 +348,275 @@ async fn test_connection_debug_format() {
     assert!(debug_str.contains("OdbcConnection"), "Debug should show struct name");
     assert!(debug_str.contains("transaction_depth"), "Debug should show transaction_depth");
 }
+
+#[tokio::test]
+async fn test_begin_with_isolation_level() {
+    use sqlx_odbc::odbc::{OdbcIsolationLevel, OdbcTransactionOptions};
+    use sqlx_odbc::sqlx_core::row::Row;
+
+    let mut conn = connect().await;
+
+    let options = OdbcTransactionOptions::new().isolation_level(OdbcIsolationLevel::Serializable);
+    let mut tx = conn.begin_with_options(options).await.expect("Begin should succeed");
+
+    let row = (&mut *tx).fetch_one(ISOLATION_QUERY).await.expect("Query should succeed");
+    assert_eq!(row.try_get::<i16, _>("level").unwrap(), 4, "Should be SERIALIZABLE");
+
+    tx.commit().await.expect("Commit should succeed");
+
+    let row = conn.fetch_one(ISOLATION_QUERY).await.expect("Query should succeed");
+    assert_eq!(row.try_get::<i16, _>("level").unwrap(), 2, "Should be back to READ COMMITTED");
+}
+
+#[tokio::test]
+async fn test_set_current_catalog() {
+    use sqlx_odbc::odbc::OdbcAttribute;
+
+    let mut conn = connect().await;
+
+    conn.set_attribute(OdbcAttribute::CURRENT_CATALOG, "tempdb")
+        .await
+        .expect("Setting the catalog should succeed");
+
+    let catalog = conn
+        .get_attribute(OdbcAttribute::CURRENT_CATALOG)
+        .await
+        .expect("Getting the catalog should succeed");
+    assert_eq!(catalog.as_str(), Some("tempdb"));
+}
+
+#[tokio::test]
+async fn test_pre_and_post_connect_attributes() {
+    use sqlx_odbc::odbc::OdbcAttribute;
+
+    let options = OdbcConnectOptions::new(get_connection_string())
+        .pre_connect_attribute(OdbcAttribute::PACKET_SIZE, 8192u32)
+        .post_connect_attribute(OdbcAttribute::CURRENT_CATALOG, "tempdb");
+    let mut conn = OdbcConnection::establish(&options)
+        .await
+        .expect("Failed to connect to database");
+
+    let catalog = conn
+        .get_attribute(OdbcAttribute::CURRENT_CATALOG)
+        .await
+        .expect("Getting the catalog should succeed");
+    assert_eq!(catalog.as_str(), Some("tempdb"));
+}
+
+#[tokio::test]
+async fn test_init_statements() {
+    let options = OdbcConnectOptions::new(get_connection_string())
+        .init_statements(["SET NOCOUNT ON", "CREATE TABLE #init_test (id INT)"]);
+    let mut conn = OdbcConnection::establish(&options)
+        .await
+        .expect("Failed to connect to database");
+
+    conn.execute_raw("INSERT INTO #init_test VALUES (1)")
+        .await
+        .expect("Temp table from init statement should exist");
+}
+
+#[tokio::test]
+async fn test_init_statement_failure() {
+    let options = OdbcConnectOptions::new(get_connection_string())
+        .init_statements(["SET NOCOUNT ON", "THIS IS NOT VALID SQL"]);
+    let err = OdbcConnection::establish(&options)
+        .await
+        .expect_err("Invalid init statement should fail the connection");
+
+    assert!(err.to_string().contains("THIS IS NOT VALID SQL"), "Error should name the statement: {err}");
+}
+
+#[tokio::test]
+async fn test_sql_error_does_not_break_connection() {
+    let mut conn = connect().await;
+
+    let err = conn
+        .execute_raw("SELECT * FROM nonexistent_table_xyz")
+        .await
+        .expect_err("Query on missing table should return error");
+    let db_err = err.as_database_error().expect("Should be a database error");
+    assert_eq!(db_err.code().as_deref(), Some("42S02"));
+
+    assert!(!conn.is_broken(), "Ordinary SQL errors should not break the connection");
+    conn.ping().await.expect("Ping should succeed");
+}
+
+#[tokio::test]
+async fn test_reset_discards_session_state() {
+    use sqlx_odbc::odbc::OdbcAttribute;
+
+    let mut conn = connect().await;
+
+    conn.execute_raw("CREATE TABLE #reset_test (id INT)")
+        .await
+        .expect("Create temp table should succeed");
+    conn.set_attribute(OdbcAttribute::CURRENT_CATALOG, "tempdb")
+        .await
+        .expect("Setting the catalog should succeed");
+    let tx = conn.begin().await.expect("Begin should succeed");
+    std::mem::forget(tx);
+
+    conn.reset().await.expect("Reset should succeed");
+
+    let catalog = conn
+        .get_attribute(OdbcAttribute::CURRENT_CATALOG)
+        .await
+        .expect("Getting the catalog should succeed");
+    assert_eq!(catalog.as_str(), Some("master"));
+    assert!(
+        conn.execute_raw("SELECT * FROM #reset_test").await.is_err(),
+        "Temp table should be gone after reset"
+    );
+    conn.begin().await.expect("Begin after reset should succeed");
+}
+
+#[tokio::test]
+async fn test_reset_after_dropped_transaction_with_options() {
+    use sqlx_odbc::odbc::{OdbcIsolationLevel, OdbcTransactionOptions};
+    use sqlx_odbc::sqlx_core::row::Row;
+
+    let mut conn = connect().await;
+
+    let options = OdbcTransactionOptions::new().isolation_level(OdbcIsolationLevel::Serializable);
+    let tx = conn.begin_with_options(options).await.expect("Begin should succeed");
+    drop(tx);
+
+    conn.reset().await.expect("Reset should succeed");
+
+    let row = conn.fetch_one(ISOLATION_QUERY).await.expect("Query should succeed");
+    assert_eq!(
+        row.try_get::<i16, _>("level").unwrap(),
+        2,
+        "Reset should restore the isolation level the dropped transaction replaced"
+    );
+}
+
+#[tokio::test]
+async fn test_nested_transaction_savepoints() {
+    let mut conn = connect().await;
+
+    conn.execute_raw("CREATE TABLE #nested_test (id INT)")
+        .await
+        .expect("Create temp table should succeed");
+
+    let mut tx = conn.begin().await.expect("Begin should succeed");
+    tx.execute("INSERT INTO #nested_test VALUES (1)").await.unwrap();
+
+    let mut inner = tx.begin().await.expect("Nested begin should succeed");
+    inner.execute("INSERT INTO #nested_test VALUES (2)").await.unwrap();
+    inner.rollback().await.expect("Nested rollback should succeed");
+
+    let mut inner = tx.begin().await.expect("Nested begin should succeed");
+    inner.execute("INSERT INTO #nested_test VALUES (3)").await.unwrap();
+    inner.commit().await.expect("Nested commit should succeed");
+
+    tx.commit().await.expect("Commit should succeed");
+
+    let rows = conn
+        .fetch_all("SELECT id FROM #nested_test ORDER BY id")
+        .await
+        .expect("Query should succeed");
+    assert_eq!(rows.len(), 2, "Only the rolled-back savepoint's row should be gone");
+}
+
+#[tokio::test]
+async fn test_dropped_transaction_rolls_back() {
+    let mut conn = connect().await;
+
+    conn.execute_raw("CREATE TABLE #drop_test (id INT)")
+        .await
+        .expect("Create temp table should succeed");
+
+    {
+        let mut tx = conn.begin().await.expect("Begin should succeed");
+        tx.execute("INSERT INTO #drop_test VALUES (1)").await.unwrap();
+        let mut inner = tx.begin().await.expect("Nested begin should succeed");
+        inner.execute("INSERT INTO #drop_test VALUES (2)").await.unwrap();
+    }
+    assert!(!conn.is_in_transaction(), "Dropping should end the transaction");
+
+    let rows = conn
+        .fetch_all("SELECT id FROM #drop_test")
+        .await
+        .expect("Query should succeed");
+    assert!(rows.is_empty(), "Dropped transaction should be rolled back");
+
+    // Autocommit is back on, so this insert survives without a commit
+    conn.execute_raw("INSERT INTO #drop_test VALUES (3)").await.unwrap();
+    conn.begin().await.unwrap().rollback().await.unwrap();
+    let rows = conn.fetch_all("SELECT id FROM #drop_test").await.unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[tokio::test]
+async fn test_begin_with_custom_statement() {
+    use sqlx_odbc::sqlx_core::error::Error;
+
+    let mut conn = connect().await;
+
+    conn.execute_raw("CREATE TABLE #begin_with_test (id INT)")
+        .await
+        .expect("Create temp table should succeed");
+
+    let mut tx = conn
+        .begin_with("BEGIN TRANSACTION")
+        .await
+        .expect("Custom begin should succeed");
+    tx.execute("INSERT INTO #begin_with_test VALUES (1)").await.unwrap();
+    assert!(matches!(
+        tx.begin_with("BEGIN TRANSACTION").await,
+        Err(Error::InvalidSavePointStatement)
+    ));
+    tx.rollback().await.expect("Rollback should succeed");
+
+    let rows = conn.fetch_all("SELECT id FROM #begin_with_test").await.unwrap();
+    assert!(rows.is_empty(), "Custom transaction should be rolled back");
+
+    assert!(matches!(conn.begin_with("SELECT 1").await, Err(Error::BeginFailed)));
+}
+
+#[tokio::test]
+async fn test_transaction_status_after_xact_abort() {
+    use sqlx_odbc::odbc::OdbcTransactionState;
+    use sqlx_odbc::sqlx_core::row::Row;
+
+    let mut conn = connect().await;
+
+    let status = conn.transaction_status().await.expect("Status should succeed");
+    assert_eq!(status.server, OdbcTransactionState::Idle);
+
+    conn.execute_raw("SET XACT_ABORT ON").await.unwrap();
+    conn.execute_raw("CREATE TABLE #xact_abort_test (id INT)").await.unwrap();
+
+    let mut tx = conn.begin().await.expect("Begin should succeed");
+    tx.execute("INSERT INTO #xact_abort_test VALUES (1)").await.unwrap();
+    let status = tx.transaction_status().await.unwrap();
+    assert!(status.is_committable(), "Transaction should be committable: {status:?}");
+
+    // Under XACT_ABORT the error dooms the transaction: it can only be rolled back. SQL Server
+    // only reports that state until the batch ends, so it is read in the same batch.
+    let row = (&mut *tx)
+        .fetch_one(
+            "BEGIN TRY DECLARE @x INT = 1 / 0 END TRY \
+             BEGIN CATCH \
+                 DECLARE @state SMALLINT = XACT_STATE(); \
+                 BEGIN TRY COMMIT END TRY \
+                 BEGIN CATCH SELECT @state AS state, ERROR_NUMBER() AS commit_error END CATCH \
+             END CATCH",
+        )
+        .await
+        .expect("Batch should return the transaction state");
+    assert_eq!(row.try_get::<i16, _>("state").unwrap(), -1, "Transaction should be doomed");
+    // 3930: the current transaction cannot be committed
+    assert_eq!(row.try_get::<i32, _>("commit_error").unwrap(), 3930);
+
+    // The server rolled it back at the end of the batch, so it is no longer committable
+    let status = tx.transaction_status().await.unwrap();
+    assert!(!status.is_committable(), "Transaction should not be committable: {status:?}");
+    assert!(status.is_out_of_sync(), "Server should have rolled back: {status:?}");
+
+    tx.rollback().await.expect("Rollback should succeed");
+    let rows = conn.fetch_all("SELECT id FROM #xact_abort_test").await.unwrap();
+    assert!(rows.is_empty(), "The insert should be rolled back");
+}
@@ -0,0 +1,33 @@
+//! ODBC environment tests.
+//!
+//! These tests require an ODBC driver manager but no database. They live in their own test
+//! binary because the environment can only be configured once per process.
+
+use sqlx_odbc::odbc::{
+    OdbcConnectOptions, OdbcConnection, OdbcConnectionPooling, OdbcEnvironmentOptions,
+    OdbcPoolingMatch, OdbcVersion,
+};
+
+#[tokio::test]
+async fn test_environment_configured_once() {
+    let configured = OdbcEnvironmentOptions::new()
+        .odbc_version(OdbcVersion::V3)
+        .connection_pooling(OdbcConnectionPooling::OnePerDriver)
+        .pooling_match(OdbcPoolingMatch::Relaxed);
+    assert_eq!(OdbcEnvironmentOptions::current(), None);
+
+    configured.clone().init().expect("First init should succeed");
+    assert_eq!(OdbcEnvironmentOptions::current(), Some(configured.clone()));
+
+    let err = OdbcEnvironmentOptions::new()
+        .odbc_version(OdbcVersion::V3_80)
+        .connection_pooling(OdbcConnectionPooling::DriverAware)
+        .init()
+        .expect_err("Second init with different options should fail");
+    assert!(err.to_string().contains("already initialized"), "Unexpected error: {err}");
+
+    // Connecting uses the configured environment rather than creating a default one
+    let options = OdbcConnectOptions::new("Driver={NonExistent};Server=invalid");
+    assert!(OdbcConnection::establish(&options).await.is_err());
+    assert_eq!(OdbcEnvironmentOptions::current(), Some(configured));
+}
@@ -0,0 +1,85 @@
+//! Tests for the `json` type integration.
+
+#![cfg(feature = "json")]
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx_odbc::odbc::types::Json;
+use sqlx_odbc::odbc::{
+    Odbc, OdbcArgumentValue, OdbcColumn, OdbcRow, OdbcTypeInfo, OdbcValue, OdbcValueData,
+    OdbcValueRef,
+};
+use sqlx_odbc::sqlx_core::decode::Decode;
+use sqlx_odbc::sqlx_core::encode::{Encode, IsNull};
+use sqlx_odbc::sqlx_core::error::Error;
+use sqlx_odbc::sqlx_core::row::Row;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    theme: String,
+    volume: u8,
+}
+
+fn row(text: &str) -> OdbcRow {
+    let type_info = OdbcTypeInfo::varchar(4000);
+    OdbcRow::new(
+        vec![OdbcColumn::new(0, "settings", type_info.clone())],
+        vec![OdbcValue::new(OdbcValueData::Text(text.into()), type_info)],
+    )
+}
+
+#[test]
+fn test_encode_as_text() {
+    let mut buf = Vec::new();
+    let settings = Json(Settings { theme: "dark".into(), volume: 7 });
+    assert!(matches!(settings.encode_by_ref(&mut buf), Ok(IsNull::No)));
+    assert!(matches!(json!([1, null]).encode_by_ref(&mut buf), Ok(IsNull::No)));
+
+    let texts: Vec<_> = buf
+        .iter()
+        .map(|arg| match arg {
+            OdbcArgumentValue::Text(s) => s.as_ref(),
+            _ => panic!("JSON should encode as text"),
+        })
+        .collect();
+    assert_eq!(texts, [r#"{"theme":"dark","volume":7}"#, "[1,null]"]);
+}
+
+#[test]
+fn test_decode_text_and_binary() {
+    let text = OdbcValueData::Text(r#"{"theme":"light","volume":3}"#.into());
+    let Json(settings) =
+        <Json<Settings> as Decode<Odbc>>::decode(OdbcValueRef::new(&text, OdbcTypeInfo::varchar(4000)))
+            .unwrap();
+    assert_eq!(settings, Settings { theme: "light".into(), volume: 3 });
+
+    let binary = OdbcValueData::Binary(br#"{"a":[1,2]}"#.to_vec());
+    let value =
+        <Value as Decode<Odbc>>::decode(OdbcValueRef::new(&binary, OdbcTypeInfo::varbinary(100)))
+            .unwrap();
+    assert_eq!(value, json!({ "a": [1, 2] }));
+}
+
+#[test]
+fn test_invalid_json_is_a_decode_error() {
+    let row = row(r#"{"theme":"dark","volume":300}"#);
+    let err = row.try_get::<Json<Settings>, _>("settings").unwrap_err();
+    assert!(matches!(err, Error::ColumnDecode { .. }), "{err:?}");
+
+    assert!(row.try_get::<Value, _>("settings").is_ok());
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_from_row_json_field() {
+    use sqlx_odbc::FromRow;
+
+    #[derive(FromRow)]
+    struct Profile {
+        #[sqlx(json)]
+        settings: Settings,
+    }
+
+    let profile = Profile::from_row(&row(r#"{"theme":"dark","volume":7}"#)).unwrap();
+    assert_eq!(profile.settings, Settings { theme: "dark".into(), volume: 7 });
+}
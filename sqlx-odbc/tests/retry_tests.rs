@@ -0,0 +1,100 @@
+//! Retry policy tests for the ODBC driver.
+//!
+//! These tests do not require a database.
+
+use odbc_api::handles::{Record, State};
+use sqlx_odbc::odbc::{OdbcDatabaseError, OdbcDialect, OdbcRetryPolicy};
+use sqlx_odbc::sqlx_core::error::Error;
+use std::time::Duration;
+
+fn io_error() -> Error {
+    Error::Io(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset"))
+}
+
+/// A database error as the driver would report it in a diagnostic record.
+fn database_error(sqlstate: &[u8; 5], native_error: i32) -> Error {
+    let record = Record {
+        state: State(*sqlstate),
+        native_error,
+        ..Default::default()
+    };
+    let error = odbc_api::Error::Diagnostics { record, function: "SQLExecDirect" };
+    Error::Database(Box::new(OdbcDatabaseError::new(error)))
+}
+
+/// A database error raised by a DBMS of the given dialect.
+fn dialect_error(sqlstate: &[u8; 5], native_error: i32, dialect: OdbcDialect) -> Error {
+    let Error::Database(error) = database_error(sqlstate, native_error) else {
+        unreachable!();
+    };
+    let error = error.try_downcast::<OdbcDatabaseError>().unwrap();
+    Error::Database(Box::new(error.with_dialect(dialect)))
+}
+
+#[test]
+fn test_retry_delay_backs_off_exponentially() {
+    let policy = OdbcRetryPolicy::new().backoff(Duration::from_millis(10), Duration::from_millis(35));
+
+    assert_eq!(policy.delay(1), Duration::from_millis(10));
+    assert_eq!(policy.delay(2), Duration::from_millis(20));
+    assert_eq!(policy.delay(3), Duration::from_millis(35));
+    assert_eq!(policy.delay(100), Duration::from_millis(35));
+}
+
+#[test]
+fn test_default_classification() {
+    let policy = OdbcRetryPolicy::new();
+
+    assert!(policy.should_retry(&io_error()));
+    assert!(!policy.should_retry(&Error::Protocol("syntax error".into())));
+    assert!(!policy.should_retry(&Error::RowNotFound));
+}
+
+#[test]
+fn test_custom_classification() {
+    let policy = OdbcRetryPolicy::new().retry_if(|e| matches!(e, Error::RowNotFound));
+
+    assert!(policy.should_retry(&Error::RowNotFound));
+    assert!(!policy.should_retry(&io_error()));
+}
+
+#[test]
+fn test_database_error_classification() {
+    let policy = OdbcRetryPolicy::new();
+
+    // Serialization failure, PostgreSQL deadlock and query timeout, by SQLSTATE
+    assert!(policy.should_retry(&database_error(b"40001", 0)));
+    assert!(policy.should_retry(&database_error(b"40P01", 0)));
+    assert!(policy.should_retry(&database_error(b"HYT00", 0)));
+
+    // SQL Server lock timeout, MySQL deadlock and lock wait timeout, by native error code with
+    // a generic SQLSTATE
+    assert!(policy.should_retry(&dialect_error(b"HY000", 1222, OdbcDialect::MsSql)));
+    assert!(policy.should_retry(&dialect_error(b"HY000", 1213, OdbcDialect::MySql)));
+    assert!(policy.should_retry(&dialect_error(b"HY000", 1205, OdbcDialect::MySql)));
+
+    // Unique violation and syntax error
+    assert!(!policy.should_retry(&database_error(b"23505", 0)));
+    assert!(!policy.should_retry(&database_error(b"42000", 102)));
+}
+
+#[test]
+fn test_database_error_keeps_diagnostics() {
+    let Error::Database(error) = database_error(b"40001", 1205) else {
+        unreachable!();
+    };
+    let error = error.downcast_ref::<OdbcDatabaseError>();
+    assert_eq!(error.sqlstate(), Some("40001"));
+    assert_eq!(error.native_error(), Some(1205));
+}
+
+#[test]
+fn test_native_error_only_retried_for_its_dialect() {
+    let policy = OdbcRetryPolicy::new();
+
+    assert!(policy.should_retry(&dialect_error(b"HY000", 1205, OdbcDialect::MsSql)));
+    assert!(!policy.should_retry(&dialect_error(b"HY000", 1205, OdbcDialect::PostgreSql)));
+    assert!(!policy.should_retry(&dialect_error(b"HY000", 1205, OdbcDialect::Oracle)));
+    // Without a dialect, the code means nothing
+    assert!(!policy.should_retry(&database_error(b"HY000", 1205)));
+}
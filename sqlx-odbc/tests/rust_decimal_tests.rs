@@ -0,0 +1,42 @@
+//! Tests for the `rust_decimal` type integration.
+
+#![cfg(feature = "rust_decimal")]
+
+mod common;
+
+use common::{encode, try_decode};
+use rust_decimal::Decimal;
+use sqlx_odbc::odbc::{OdbcArgumentValue, OdbcTypeInfo, OdbcValueData};
+use std::str::FromStr;
+
+fn decode(text: &str, type_info: OdbcTypeInfo) -> Result<Decimal, String> {
+    try_decode(OdbcValueData::Text(text.into()), type_info)
+}
+
+#[test]
+fn test_decode_keeps_precision_and_column_scale() {
+    assert_eq!(
+        decode("12345678901234567890.1234567", OdbcTypeInfo::numeric(38, 7)).unwrap(),
+        Decimal::from_str("12345678901234567890.1234567").unwrap()
+    );
+
+    let value = decode("1.5", OdbcTypeInfo::numeric(10, 2)).unwrap();
+    assert_eq!(value.to_string(), "1.50");
+}
+
+#[test]
+fn test_decode_out_of_range_scale() {
+    let err = decode("0.1", OdbcTypeInfo::numeric(38, 30)).unwrap_err();
+    assert!(err.contains("scale 30"), "error should name the scale: {err}");
+
+    assert!(decode("not a number", OdbcTypeInfo::numeric(10, 2)).is_err());
+}
+
+#[test]
+fn test_encode_as_exact_text() {
+    let value = Decimal::from_str("-0.000000000000000000000000001").unwrap();
+    assert!(matches!(
+        encode(value),
+        OdbcArgumentValue::Text(s) if s == "-0.000000000000000000000000001"
+    ));
+}
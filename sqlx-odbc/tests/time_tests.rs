@@ -0,0 +1,89 @@
+//! Tests for the `time` type integration.
+
+#![cfg(feature = "time")]
+
+mod common;
+
+use common::{decode, encode, try_decode, try_encode};
+use sqlx_odbc::odbc::{OdbcArgumentValue, OdbcTypeInfo, OdbcValueData};
+use time::macros::{date, datetime, time};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+#[test]
+fn test_timestamp_round_trip_keeps_nanoseconds() {
+    let value = datetime!(2024-02-29 13:14:15.123456789);
+
+    let OdbcArgumentValue::Timestamp(ts) = encode(value) else {
+        panic!("PrimitiveDateTime should encode as a timestamp");
+    };
+    assert_eq!(ts.fraction, 123_456_789);
+    assert_eq!(
+        decode::<PrimitiveDateTime>(OdbcValueData::Timestamp(ts), OdbcTypeInfo::timestamp(7)),
+        value
+    );
+}
+
+#[test]
+fn test_decode_iso_text() {
+    assert_eq!(
+        decode::<Date>(OdbcValueData::Text("2024-02-29".into()), OdbcTypeInfo::date()),
+        date!(2024-02-29)
+    );
+    assert_eq!(
+        decode::<Time>(OdbcValueData::Text("13:14:15.1234567".into()), OdbcTypeInfo::time(7)),
+        time!(13:14:15.1234567)
+    );
+    assert_eq!(
+        decode::<PrimitiveDateTime>(
+            OdbcValueData::Text("2024-02-29T13:14:15".into()),
+            OdbcTypeInfo::timestamp(7)
+        ),
+        datetime!(2024-02-29 13:14:15)
+    );
+    assert_eq!(
+        decode::<OffsetDateTime>(
+            OdbcValueData::Text("2024-02-29 13:14:15 +02:00".into()),
+            OdbcTypeInfo::timestamp_offset(7)
+        ),
+        datetime!(2024-02-29 13:14:15 +02:00)
+    );
+    assert_eq!(
+        decode::<OffsetDateTime>(
+            OdbcValueData::Text("2024-02-29 13:14:15".into()),
+            OdbcTypeInfo::timestamp_offset(7)
+        ),
+        datetime!(2024-02-29 13:14:15 UTC)
+    );
+}
+
+#[test]
+fn test_offset_date_time_keeps_offset() {
+    let value = datetime!(2024-02-29 13:14:15.5 -03:30);
+
+    let OdbcArgumentValue::TimestampOffset(ts) = encode(value) else {
+        panic!("OffsetDateTime should encode as a timestamp with offset");
+    };
+    assert_eq!((ts.hour, ts.timezone_hour, ts.timezone_minute), (13, -3, -30));
+    assert_eq!(
+        decode::<OffsetDateTime>(
+            OdbcValueData::TimestampOffset(ts),
+            OdbcTypeInfo::timestamp_offset(7)
+        ),
+        value
+    );
+}
+
+#[test]
+fn test_invalid_text_is_rejected() {
+    let invalid = OdbcValueData::Text("2023-02-29".into());
+    assert!(try_decode::<Date>(invalid, OdbcTypeInfo::date()).is_err());
+}
+
+#[test]
+fn test_out_of_range_dates_are_rejected() {
+    for year in [-1, 0] {
+        let date = Date::from_calendar_date(year, Month::January, 1).unwrap();
+        let err = try_encode(date).expect_err("year should be out of range");
+        assert!(err.contains("out of range for DATE"), "Unexpected error: {err}");
+    }
+}
@@ -0,0 +1,85 @@
+//! Tests for built-in type handling.
+
+use sqlx_odbc::odbc::{
+    Odbc, OdbcArgumentValue, OdbcTimestampOffset, OdbcTypeInfo, OdbcValueData, OdbcValueRef,
+};
+use sqlx_odbc::sqlx_core::decode::Decode;
+use sqlx_odbc::sqlx_core::encode::{Encode, IsNull};
+use sqlx_odbc::sqlx_core::type_info::TypeInfo;
+
+#[test]
+fn test_timestamp_offset_parse() {
+    let ts: OdbcTimestampOffset = "2024-02-29 13:14:15.1234567 +05:30".parse().unwrap();
+    assert_eq!((ts.year, ts.month, ts.day), (2024, 2, 29));
+    assert_eq!((ts.hour, ts.minute, ts.second, ts.fraction), (13, 14, 15, 123_456_700));
+    assert_eq!(ts.offset_seconds(), 5 * 3600 + 30 * 60);
+
+    // PostgreSQL `timestamptz` text
+    let ts: OdbcTimestampOffset = "2024-02-29 13:14:15-08".parse().unwrap();
+    assert_eq!((ts.timezone_hour, ts.timezone_minute, ts.fraction), (-8, 0, 0));
+
+    let ts: OdbcTimestampOffset = "2024-02-29T13:14:15Z".parse().unwrap();
+    assert_eq!(ts.offset_seconds(), 0);
+
+    assert!("2024-02-29 13:14:15".parse::<OdbcTimestampOffset>().is_err());
+    assert!("not a timestamp".parse::<OdbcTimestampOffset>().is_err());
+}
+
+#[test]
+fn test_timestamp_offset_display_round_trip() {
+    let ts: OdbcTimestampOffset = "2024-02-29 13:14:15.5 -03:30".parse().unwrap();
+    assert_eq!(ts.to_string(), "2024-02-29 13:14:15.500000000 -03:30");
+    assert_eq!(ts.to_string().parse::<OdbcTimestampOffset>().unwrap(), ts);
+
+    let data = OdbcValueData::TimestampOffset(ts);
+    let value = OdbcValueRef::new(&data, OdbcTypeInfo::timestamp_offset(7));
+    assert_eq!(<String as Decode<Odbc>>::decode(value).unwrap(), ts.to_string());
+}
+
+#[test]
+fn test_timestamp_offset_type_info() {
+    let ty = OdbcTypeInfo::timestamp_offset(7);
+    assert!(ty.is_timestamp_offset());
+    assert_eq!(ty.name(), "DATETIMEOFFSET");
+    assert!(!OdbcTypeInfo::timestamp(7).is_timestamp_offset());
+}
+
+#[test]
+fn test_unsigned_decode_is_range_checked() {
+    fn decode<T: for<'r> Decode<'r, Odbc>>(data: OdbcValueData) -> Result<T, String> {
+        T::decode(OdbcValueRef::new(&data, OdbcTypeInfo::tiny_int())).map_err(|e| e.to_string())
+    }
+
+    // SQL Server TINYINT is unsigned
+    assert_eq!(decode::<u8>(OdbcValueData::Text("200".into())), Ok(200));
+    assert_eq!(decode::<u8>(OdbcValueData::TinyInt(200u8 as i8)), Ok(200));
+    assert!(decode::<i8>(OdbcValueData::Text("200".into())).is_err());
+
+    assert_eq!(decode::<u16>(OdbcValueData::Int(65_535)), Ok(u16::MAX));
+    assert_eq!(decode::<u64>(OdbcValueData::Text(u64::MAX.to_string())), Ok(u64::MAX));
+
+    assert!(decode::<u8>(OdbcValueData::SmallInt(256)).unwrap_err().contains("out of range"));
+    assert!(decode::<u32>(OdbcValueData::Int(-1)).is_err());
+    assert!(decode::<u64>(OdbcValueData::BigInt(-1)).is_err());
+    assert!(decode::<u16>(OdbcValueData::Text("-5".into())).is_err());
+}
+
+#[test]
+fn test_unsigned_encode_uses_wider_signed_types() {
+    let mut buf = Vec::new();
+    assert!(matches!(u8::MAX.encode_by_ref(&mut buf), Ok(IsNull::No)));
+    assert!(matches!(u16::MAX.encode_by_ref(&mut buf), Ok(IsNull::No)));
+    assert!(matches!(u32::MAX.encode_by_ref(&mut buf), Ok(IsNull::No)));
+    assert!(matches!((i64::MAX as u64).encode_by_ref(&mut buf), Ok(IsNull::No)));
+    assert!(matches!(
+        buf.as_slice(),
+        [
+            OdbcArgumentValue::SmallInt(255),
+            OdbcArgumentValue::Int(65_535),
+            OdbcArgumentValue::BigInt(4_294_967_295),
+            OdbcArgumentValue::BigInt(i64::MAX),
+        ]
+    ));
+
+    assert!(u64::MAX.encode_by_ref(&mut buf).is_err());
+}
@@ -0,0 +1,65 @@
+//! Tests for the `uuid` type integration.
+
+#![cfg(feature = "uuid")]
+
+mod common;
+
+use common::{connect, decode, encode, try_decode};
+use sqlx_odbc::odbc::{OdbcArgumentValue, OdbcTypeInfo, OdbcValueData};
+use sqlx_odbc::sqlx_core::executor::Executor;
+use sqlx_odbc::sqlx_core::row::Row;
+use uuid::Uuid;
+
+const UUID: &str = "6f9619ff-8b86-d011-b42d-00c04fc964ff";
+
+#[test]
+fn test_encode_as_guid_round_trip() {
+    let uuid = Uuid::parse_str(UUID).unwrap();
+    let OdbcArgumentValue::Guid(guid) = encode(uuid) else {
+        panic!("Uuid should encode as a GUID");
+    };
+    assert_eq!((guid.d1, guid.d2, guid.d3), (0x6f9619ff, 0x8b86, 0xd011));
+    assert_eq!(decode::<Uuid>(OdbcValueData::Guid(guid), OdbcTypeInfo::guid()), uuid);
+}
+
+#[test]
+fn test_decode_text() {
+    let expected = Uuid::parse_str(UUID).unwrap();
+    assert_eq!(
+        decode::<Uuid>(OdbcValueData::Text(UUID.to_uppercase()), OdbcTypeInfo::guid()),
+        expected
+    );
+    assert_eq!(
+        decode::<Uuid>(OdbcValueData::Text(format!("{{{UUID}}}")), OdbcTypeInfo::varchar(38)),
+        expected
+    );
+}
+
+#[test]
+fn test_decode_binary_byte_order() {
+    let expected = Uuid::parse_str(UUID).unwrap();
+
+    // Plain binary columns hold RFC 4122 order
+    let rfc = expected.as_bytes().to_vec();
+    assert_eq!(decode::<Uuid>(OdbcValueData::Binary(rfc), OdbcTypeInfo::varbinary(16)), expected);
+
+    // GUID columns hold the mixed-endian `GUID` layout
+    let le = expected.to_bytes_le().to_vec();
+    assert_eq!(&le[..4], [0xff, 0x19, 0x96, 0x6f]);
+    assert_eq!(decode::<Uuid>(OdbcValueData::Binary(le), OdbcTypeInfo::guid()), expected);
+
+    let short = OdbcValueData::Binary(vec![0; 4]);
+    assert!(try_decode::<Uuid>(short, OdbcTypeInfo::varbinary(16)).is_err());
+}
+
+/// Requires a running MS SQL Server instance, see `connection_tests.rs`.
+#[tokio::test]
+async fn test_decode_uniqueidentifier_column() {
+    let mut conn = connect().await;
+
+    let row = conn
+        .fetch_one(format!("SELECT CAST('{UUID}' AS UNIQUEIDENTIFIER) AS id").as_str())
+        .await
+        .expect("Query should succeed");
+    assert_eq!(row.try_get::<Uuid, _>("id").unwrap(), Uuid::parse_str(UUID).unwrap());
+}